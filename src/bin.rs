@@ -6,6 +6,13 @@ impl ResourceSizeTable {
     /// Reads an RSTB from a byte slice. Will automatically decompress yaz0
     /// data if the `yaz0` feature is enabled.
     pub fn from_binary<B: Borrow<[u8]>>(bytes: B) -> Result<Self> {
+        Self::from_binary_with_endian(bytes).map(|(table, _)| table)
+    }
+
+    /// Reads an RSTB from a byte slice as with
+    /// [`from_binary()`](Self::from_binary), also returning the byte order it
+    /// was stored in so it can be written back the same way.
+    pub fn from_binary_with_endian<B: Borrow<[u8]>>(bytes: B) -> Result<(Self, Endian)> {
         #[cfg(feature = "yaz0")]
         let bytes = if &bytes.borrow()[0..4] == b"Yaz0" {
             let mut reader = std::io::Cursor::new(bytes.borrow());
@@ -57,12 +64,17 @@ impl ResourceSizeTable {
         } else {
             BTreeMap::new()
         };
-        Ok(Self { crc_map, name_map })
+        Ok((Self { crc_map, name_map }, endian))
     }
 
     /// Writes the RSTB to a writer implementing `std::io::Write` with the
     /// specified endianness.
-    pub fn write<W: Write>(&self, writer: &mut W, endian: Endian) -> std::io::Result<()> {
+    pub fn write<W: Write>(
+        &self,
+        writer: &mut W,
+        endian: impl Into<Endian>,
+    ) -> std::io::Result<()> {
+        let endian = endian.into();
         write!(writer, "RSTB")?;
         write_u32(writer, self.crc_map.len() as u32, endian)?;
         write_u32(writer, self.name_map.len() as u32, endian)?;
@@ -78,7 +90,7 @@ impl ResourceSizeTable {
     }
 
     /// Writes the RSTB to an in-memory buffer using the specified endianness.
-    pub fn to_binary(&self, endian: Endian) -> Vec<u8> {
+    pub fn to_binary(&self, endian: impl Into<Endian>) -> Vec<u8> {
         let mut buf: Vec<u8> =
             Vec::with_capacity(12 + (self.crc_map.len() * 8) + (self.name_map.len() * 132));
         self.write(&mut buf, endian)
@@ -92,7 +104,7 @@ impl ResourceSizeTable {
     /// actually really slow, so it's better to compress on your own if it
     /// suits your purpose.
     #[cfg(feature = "yaz0")]
-    pub fn to_compressed_binary(&self, endian: Endian) -> Vec<u8> {
+    pub fn to_compressed_binary(&self, endian: impl Into<Endian>) -> Vec<u8> {
         let mut buf: Vec<u8> =
             Vec::with_capacity(12 + (self.crc_map.len() * 8) + (self.name_map.len() * 132));
         {
@@ -147,6 +159,17 @@ mod tests {
         assert_eq!(rstb.get("Map/MainField/A-1/A-1_Dynamic.mubin"), Some(48800))
     }
 
+    #[test]
+    fn detect_endian() {
+        let bytes = std::fs::read("test/ResourceSizeTable.product.rsizetable").unwrap();
+        let (rstb, endian) = ResourceSizeTable::from_binary_with_endian(bytes).unwrap();
+        assert_eq!(endian, crate::Endian::Big);
+        let (_, endian) =
+            ResourceSizeTable::from_binary_with_endian(rstb.to_binary(crate::Endian::Little))
+                .unwrap();
+        assert_eq!(endian, crate::Endian::Little);
+    }
+
     #[test]
     fn rstb_roundtrip() {
         let bytes = std::fs::read("test/ResourceSizeTable.product.rsizetable").unwrap();
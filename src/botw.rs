@@ -3,25 +3,32 @@ use std::{collections::HashMap, sync::LazyLock};
 use include_flate::flate;
 use serde_json::Value;
 
-use crate::{Endian, ResourceSizeTable, CRC32};
+use crate::{Platform, ResourceSizeTable, CRC32};
 
 flate!(static SWITCH_RSTB_JSON: str from "data/switch.json");
 flate!(static WIIU_RSTB_JSON: str from "data/wiiu.json");
 
 impl ResourceSizeTable {
     /// *Requires the `botw-data` feature.*
-    /// Creates a new copy of a stock BOTW RSTB. Passing `Endian::Big` will
-    /// return the RSTB from the 1.5.0 Wii U version, and passing
-    /// `Endian::Little` will return the RSTB from the 1.6.0 Switch version.
+    /// Creates a new copy of a stock BOTW RSTB: the 1.5.0 RSTB for
+    /// `Platform::WiiU` (or `Endian::Big`), and the 1.6.0 RSTB for
+    /// `Platform::Switch` (or `Endian::Little`).
     #[cfg(feature = "botw-data")]
-    pub fn new_from_stock(endian: Endian) -> Self {
-        match endian {
-            Endian::Big => WIIU_RSTB.clone(),
-            Endian::Little => SWITCH_RSTB.clone(),
+    pub fn new_from_stock(platform: impl Into<Platform>) -> Self {
+        match platform.into() {
+            Platform::WiiU => WIIU_RSTB.clone(),
+            Platform::Switch => SWITCH_RSTB.clone(),
         }
     }
 }
 
+/// *Requires the `botw-data` feature.*
+/// Looks up the resource name for a CRC hash among the resources in the
+/// stock RSTBs of both platforms.
+pub fn canonical_name(hash: u32) -> Option<&'static str> {
+    FILE_HASHES.get(&hash).map(String::as_str)
+}
+
 pub(crate) static SWITCH_RSTB: LazyLock<ResourceSizeTable> = LazyLock::new(|| {
     serde_json::from_str(SWITCH_RSTB_JSON.as_ref()).expect("Ref JSON is good, tho")
 });
@@ -54,4 +61,14 @@ mod tests {
             Some(48800)
         );
     }
+
+    #[test]
+    fn canonical_name() {
+        let name = "Map/MainField/A-1/A-1_Dynamic.mubin";
+        assert_eq!(
+            super::canonical_name(crate::CRC32.checksum(name.as_bytes())),
+            Some(name)
+        );
+        assert_eq!(super::canonical_name(0), None);
+    }
 }
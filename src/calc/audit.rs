@@ -0,0 +1,271 @@
+//! Checks the accuracy of the calculations against a game dump.
+//!
+//! [`audit()`] walks a content folder, including the files nested in packs
+//! and other archives, and compares the value calculated or estimated for
+//! every supported resource with its value in the stock RSTB. Each resource
+//! is only checked once, the first time it is found.
+//!
+//! ```no_run
+//! use rstb::{calc::audit, Platform, ResourceSizeTable};
+//!
+//! let root = "/games/botw/content";
+//! let stock = ResourceSizeTable::from_binary(std::fs::read(format!(
+//!     "{root}/System/Resource/ResourceSizeTable.product.srsizetable"
+//! ))?)?;
+//! let report = audit::audit(root, &stock, Platform::WiiU)?;
+//! for (ext, stats) in &report.types {
+//!     println!("{ext}: {} of {} underestimated", stats.under, stats.samples);
+//! }
+//! for entry in report.worst_offenders(10) {
+//!     println!("{}: {:?} for {}", entry.name, entry.value, entry.stock);
+//! }
+//! # Ok::<(), rstb::RstbError>(())
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::Path,
+};
+
+use super::{calibrate::ErrorStats, decompress, estimate_from_bytes_and_name, resource_ext, sarc};
+use crate::{Platform, ResourceSizeTable, Result};
+
+/// The result of checking one resource against the stock RSTB.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// The resource name, as used in the RSTB.
+    pub name:  String,
+    /// The stock RSTB value.
+    pub stock: u32,
+    /// The calculated or estimated value, or `None` if the type is not
+    /// supported.
+    pub value: Option<u32>,
+}
+
+impl AuditEntry {
+    /// How far the value is below the stock value, or 0 if it is not.
+    pub fn under(&self) -> u32 {
+        self.value
+            .map_or(0, |value| self.stock.saturating_sub(value))
+    }
+
+    /// How far the value is above the stock value, or 0 if it is not.
+    pub fn over(&self) -> u32 {
+        self.value
+            .map_or(0, |value| value.saturating_sub(self.stock))
+    }
+}
+
+/// The results of an audit.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditReport {
+    /// Accuracy statistics for each resource type (e.g. `bfres`, `baiprog`).
+    pub types:   BTreeMap<String, ErrorStats>,
+    /// Every resource found that has a stock RSTB entry.
+    pub entries: Vec<AuditEntry>,
+}
+
+impl AuditReport {
+    /// Returns up to `count` entries with the largest errors, underestimates
+    /// first since those crash the game.
+    pub fn worst_offenders(&self, count: usize) -> Vec<&AuditEntry> {
+        let mut entries: Vec<&AuditEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.under() > 0 || entry.over() > 0)
+            .collect();
+        entries.sort_by_key(|entry| {
+            (
+                std::cmp::Reverse(entry.under()),
+                std::cmp::Reverse(entry.over()),
+            )
+        });
+        entries.truncate(count);
+        entries
+    }
+}
+
+/// Audits the calculations for every resource in a content folder, and in
+/// the archives it contains, against the folder's stock RSTB. Resources
+/// without a stock entry are skipped.
+pub fn audit<P: AsRef<Path>>(
+    root: P,
+    stock: &ResourceSizeTable,
+    platform: impl Into<Platform>,
+) -> Result<AuditReport> {
+    let mut auditor = Auditor {
+        stock,
+        platform: platform.into(),
+        seen: HashSet::new(),
+        entries: vec![],
+    };
+    auditor.walk(root.as_ref(), root.as_ref())?;
+    let mut types: BTreeMap<String, Vec<(Option<u32>, u32)>> = BTreeMap::new();
+    for entry in &auditor.entries {
+        if let Some(ext) = resource_ext(&entry.name) {
+            types
+                .entry(ext.to_owned())
+                .or_default()
+                .push((entry.value, entry.stock));
+        }
+    }
+    Ok(AuditReport {
+        types:   types
+            .into_iter()
+            .map(|(ext, pairs)| (ext, ErrorStats::from_pairs(pairs.into_iter())))
+            .collect(),
+        entries: auditor.entries,
+    })
+}
+
+struct Auditor<'a> {
+    stock:    &'a ResourceSizeTable,
+    platform: Platform,
+    seen:     HashSet<String>,
+    entries:  Vec<AuditEntry>,
+}
+
+impl Auditor<'_> {
+    fn walk(&mut self, root: &Path, dir: &Path) -> Result<()> {
+        let mut paths = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        paths.sort();
+        for path in paths {
+            if path.is_dir() {
+                self.walk(root, &path)?;
+            } else if let Some(name) = path.strip_prefix(root).ok().and_then(|path| path.to_str()) {
+                let name = name.replace('\\', "/");
+                self.check(&name, &std::fs::read(&path)?);
+            }
+        }
+        Ok(())
+    }
+
+    fn check(&mut self, name: &str, bytes: &[u8]) {
+        if let Some(name) = self.stock_name(name) {
+            if self.seen.insert(name.clone()) {
+                let value = if bytes.len() < 8 {
+                    None
+                } else {
+                    estimate_from_bytes_and_name(bytes, &name, self.platform)
+                };
+                let stock = self.stock.get(name.as_str()).unwrap_or_default();
+                self.entries.push(AuditEntry { name, stock, value });
+            }
+        }
+        if let Some(data) = decompress::yaz0(bytes) {
+            if let Some(files) = sarc::files(&data) {
+                for (nested, bytes) in files {
+                    self.check(&nested, bytes);
+                }
+            }
+        }
+    }
+
+    /// Finds the name a file has in the stock RSTB, which drops the `s` of
+    /// Yaz0 compressed extensions.
+    fn stock_name(&self, name: &str) -> Option<String> {
+        if self.stock.contains(name) {
+            return Some(name.to_owned());
+        }
+        let dot = name.find('.')?;
+        let canonical = format!("{}.{}", &name[..dot], resource_ext(name)?);
+        self.stock.contains(canonical.as_str()).then_some(canonical)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audit_nested() {
+        let root = std::env::temp_dir().join(format!("rstb_audit_test_{}", std::process::id()));
+        let actor = root.join("Actor/Pack");
+        std::fs::create_dir_all(&actor).unwrap();
+        std::fs::copy(
+            "test/AirOcta_Tag.sbactorpack",
+            actor.join("AirOcta_Tag.sbactorpack"),
+        )
+        .unwrap();
+        let mut stock = ResourceSizeTable::default();
+        stock.set("Actor/Pack/AirOcta_Tag.bactorpack", 0x2000);
+        stock.set("Actor/ActorLink/AirOcta_Tag.bxml", 1);
+        let report = audit(&root, &stock, Platform::Switch).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(report.entries.len(), 2);
+        assert_eq!(report.types["bactorpack"].samples, 1);
+        assert_eq!(report.types["bxml"].samples, 1);
+        let worst = report.worst_offenders(1);
+        assert_eq!(worst.len(), 1);
+    }
+
+    /// Audits the game dumps whose content folders are named by the
+    /// `RSTB_AUDIT_WIIU` and `RSTB_AUDIT_NX` environment variables, checking
+    /// that none of the listed types come out below their stock values.
+    /// Platforms without a dump are skipped.
+    #[cfg(feature = "complex")]
+    #[test]
+    fn audit_dump() {
+        for (var, platform, types) in [
+            (
+                "RSTB_AUDIT_WIIU",
+                Platform::WiiU,
+                &[
+                    "baiprog",
+                    "baniminfo",
+                    "baslist",
+                    "bassetting",
+                    "bbonectrl",
+                    "bdrop",
+                    "bgparamlist",
+                    "blifecondition",
+                    "bmodellist",
+                    "bphysics",
+                    "bphyssb",
+                    "brecipe",
+                    "brgbw",
+                    "brgconfiglist",
+                    "bshop",
+                    "bxml",
+                ][..],
+            ),
+            (
+                "RSTB_AUDIT_NX",
+                Platform::Switch,
+                &[
+                    "bassetting",
+                    "bbonectrl",
+                    "blifecondition",
+                    "bphyssb",
+                    "brgbw",
+                    "brgconfiglist",
+                    "bxml",
+                ][..],
+            ),
+        ] {
+            let Some(root) = std::env::var_os(var) else {
+                continue;
+            };
+            let root = Path::new(&root);
+            let bytes =
+                std::fs::read(root.join("System/Resource/ResourceSizeTable.product.srsizetable"))
+                    .unwrap();
+            let stock =
+                ResourceSizeTable::from_binary(decompress::yaz0(&bytes).unwrap().as_ref()).unwrap();
+            let report = audit(root, &stock, platform).unwrap();
+            let under: Vec<&str> = report
+                .entries
+                .iter()
+                .filter(|entry| {
+                    resource_ext(&entry.name).is_some_and(|ext| types.contains(&ext))
+                        && !entry.name.contains("Dummy")
+                        && entry.under() > 0
+                })
+                .map(|entry| entry.name.as_str())
+                .collect();
+            assert!(under.is_empty(), "{platform:?} underestimates {under:?}");
+        }
+    }
+}
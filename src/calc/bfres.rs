@@ -0,0 +1,301 @@
+//! Reads the section counts from a BFRES (`FRES`) header, for both the Wii U
+//! and Switch variants of the format, and uses the shape count to compute the
+//! model factory's runtime allocation on Wii U. For files like the ones it
+//! was measured on, this can raise the size-based
+//! [`estimate_bfres`](super::estimate_bfres) heuristic but never lower it.
+//! Switch models are only sized by the heuristic.
+//!
+//! Texture archives (`Tex.bfres`, `Tex1.bfres`, `Tex2.bfres`) are handled
+//! separately. On Wii U they cost a flat amount per texture, which covers its
+//! surface and mip levels, as the one archive measured can't split them. On
+//! Switch the textures are only read to check the archive, since no Switch
+//! archive has been measured.
+
+use super::decompress;
+use crate::{Endian, Platform};
+
+// Measured from the stock Wii U model fixtures: DgnMrgPrt_Dungeon061 has 39
+// shapes and needs 377000 bytes beyond the file, parse constant and class
+// size, and FldObj_TreeRootTropical_A_Far has 5 shapes and needs 52024. These
+// are the line through both, rounded up to 0x10. Every shape in them has its
+// own material and vertex buffer, so those can't be costed separately.
+const FILE_OVERHEAD_WIIU: u32 = 0x1090;
+const SHAPE_SIZE_WIIU: u32 = 0x2560;
+// The models in both fixtures have single bone skeletons and the archives hold
+// one embedded file, so files with more of either aren't sized by the line
+const FITTED_BONES_PER_MODEL: u32 = 1;
+const FITTED_EMBEDDED_FILES: u32 = 1;
+// Measured from the stock Wii U texture fixture: Animal_Bass.Tex1 has 8
+// textures and needs 7932 bytes beyond the file, parse constant and class
+// size, rounded up to 0x10 per texture. With one archive the cost can't be
+// split between textures, their surfaces and their mip levels, so it has no
+// separate mip or surface term.
+const TEXTURE_OBJ_SIZE_WIIU: u32 = 0x3e0;
+
+/// Section counts read from a BFRES file. Bones are only read from Wii U
+/// files, the only ones sized from their counts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BfresInfo {
+    pub models: u32,
+    pub shapes: u32,
+    pub materials: u32,
+    pub bones: u32,
+    pub embedded: u32,
+}
+
+/// Calculates the allocation made for a BFRES beyond the file itself, the
+/// parse constant and the factory size, returning `None` if the header
+/// cannot be read or the file isn't like the ones the costs were measured
+/// on: it has no shapes, more materials than shapes, more bones or embedded
+/// files than the fixtures, or is a Switch file. Those are left to the size
+/// heuristic.
+pub(crate) fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    if platform == Platform::Switch {
+        return None;
+    }
+    let info = read_info(&decompress::yaz0(bytes)?)?;
+    let fitted = info.shapes > 0
+        && info.materials <= info.shapes
+        && info.bones <= info.models.saturating_mul(FITTED_BONES_PER_MODEL)
+        && info.embedded <= FITTED_EMBEDDED_FILES;
+    fitted.then(|| FILE_OVERHEAD_WIIU.saturating_add(info.shapes.saturating_mul(SHAPE_SIZE_WIIU)))
+}
+
+/// Texture counts read from a texture archive.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TextureInfo {
+    pub textures: u32,
+}
+
+/// Calculates the allocation made for a texture archive beyond the file
+/// itself, the parse constant and the factory size, returning `None` if the
+/// textures cannot be read. No Switch archive has been measured, so they get
+/// nothing beyond the plain calculation, as before.
+pub(crate) fn parse_texture_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    let info = read_textures(&decompress::yaz0(bytes)?)?;
+    Some(match platform {
+        Platform::WiiU => info.textures.saturating_mul(TEXTURE_OBJ_SIZE_WIIU),
+        Platform::Switch => 0,
+    })
+}
+
+/// Reads the texture counts from decompressed texture archive data, using the
+/// FTEX surfaces on Wii U and the embedded BNTX on Switch.
+pub(crate) fn read_textures(bytes: &[u8]) -> Option<TextureInfo> {
+    if bytes.get(0..8)? == b"FRES    " {
+        let start = bytes
+            .chunks_exact(8)
+            .position(|chunk| chunk == b"BNTX\0\0\0\0")?
+            * 8;
+        read_textures_bntx(&bytes[start..])
+    } else if bytes.get(0..4)? == b"FRES" {
+        read_textures_wiiu(bytes)
+    } else {
+        None
+    }
+}
+
+/// Reads the section counts from decompressed BFRES data.
+pub(crate) fn read_info(bytes: &[u8]) -> Option<BfresInfo> {
+    if bytes.get(0..8)? == b"FRES    " {
+        read_info_nx(bytes)
+    } else if bytes.get(0..4)? == b"FRES" {
+        read_info_wiiu(bytes)
+    } else {
+        None
+    }
+}
+
+struct Reader<'a> {
+    bytes:  &'a [u8],
+    endian: Endian,
+}
+
+impl Reader<'_> {
+    fn u16(&self, offset: usize) -> Option<u16> {
+        let raw: [u8; 2] = self.bytes.get(offset..offset + 2)?.try_into().ok()?;
+        Some(match self.endian {
+            Endian::Big => u16::from_be_bytes(raw),
+            Endian::Little => u16::from_le_bytes(raw),
+        })
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        let raw: [u8; 4] = self.bytes.get(offset..offset + 4)?.try_into().ok()?;
+        Some(match self.endian {
+            Endian::Big => u32::from_be_bytes(raw),
+            Endian::Little => u32::from_le_bytes(raw),
+        })
+    }
+
+    fn u64(&self, offset: usize) -> Option<u64> {
+        let raw: [u8; 8] = self.bytes.get(offset..offset + 8)?.try_into().ok()?;
+        Some(match self.endian {
+            Endian::Big => u64::from_be_bytes(raw),
+            Endian::Little => u64::from_le_bytes(raw),
+        })
+    }
+
+    /// Resolves a Wii U style offset, which is relative to its own position.
+    fn relative(&self, offset: usize) -> Option<usize> {
+        match self.u32(offset)? as i32 {
+            0 => None,
+            rel => offset.checked_add_signed(rel as isize),
+        }
+    }
+
+    fn magic(&self, offset: usize, magic: &[u8; 4]) -> Option<()> {
+        (self.bytes.get(offset..offset + 4)? == magic).then_some(())
+    }
+}
+
+fn bom(bytes: &[u8], offset: usize) -> Option<Endian> {
+    match bytes.get(offset..offset + 2)? {
+        [0xfe, 0xff] => Some(Endian::Big),
+        [0xff, 0xfe] => Some(Endian::Little),
+        _ => None,
+    }
+}
+
+const WIIU_GROUPS: usize = 0x20;
+const WIIU_COUNTS: usize = 0x50;
+const WIIU_MODEL_GROUP: usize = 0;
+const WIIU_TEXTURE_GROUP: usize = 1;
+const WIIU_EMBEDDED_GROUP: usize = 11;
+
+fn read_info_wiiu(bytes: &[u8]) -> Option<BfresInfo> {
+    let reader = Reader {
+        bytes,
+        endian: bom(bytes, 0x8)?,
+    };
+    let count = |group: usize| reader.u16(WIIU_COUNTS + group * 2).map(u32::from);
+    let mut info = BfresInfo {
+        models: count(WIIU_MODEL_GROUP)?,
+        embedded: count(WIIU_EMBEDDED_GROUP)?,
+        ..Default::default()
+    };
+
+    if info.models > 0 {
+        let group = reader.relative(WIIU_GROUPS + WIIU_MODEL_GROUP * 4)?;
+        // Index group entries follow the group header and its root entry
+        for i in 1..=info.models as usize {
+            let model = reader.relative(group + 8 + i * 0x10 + 0xc)?;
+            reader.magic(model, b"FMDL")?;
+            info.shapes += reader.u16(model + 0x22)? as u32;
+            info.materials += reader.u16(model + 0x24)? as u32;
+            let skeleton = reader.relative(model + 0xc)?;
+            reader.magic(skeleton, b"FSKL")?;
+            info.bones += reader.u16(skeleton + 0x8)? as u32;
+        }
+    }
+    Some(info)
+}
+
+fn read_textures_wiiu(bytes: &[u8]) -> Option<TextureInfo> {
+    let reader = Reader {
+        bytes,
+        endian: bom(bytes, 0x8)?,
+    };
+    let info = TextureInfo {
+        textures: reader.u16(WIIU_COUNTS + WIIU_TEXTURE_GROUP * 2)? as u32,
+    };
+    if info.textures > 0 {
+        let group = reader.relative(WIIU_GROUPS + WIIU_TEXTURE_GROUP * 4)?;
+        for i in 1..=info.textures as usize {
+            let texture = reader.relative(group + 8 + i * 0x10 + 0xc)?;
+            reader.magic(texture, b"FTEX")?;
+        }
+    }
+    Some(info)
+}
+
+const BNTX_TEXTURE_COUNT: usize = 0x24;
+const BNTX_TEXTURE_ARRAY: usize = 0x28;
+
+fn read_textures_bntx(bytes: &[u8]) -> Option<TextureInfo> {
+    let reader = Reader {
+        bytes,
+        endian: bom(bytes, 0xc)?,
+    };
+    reader.magic(0x20, b"NX  ")?;
+    let info = TextureInfo {
+        textures: reader.u32(BNTX_TEXTURE_COUNT)?,
+    };
+    let textures = reader.u64(BNTX_TEXTURE_ARRAY)? as usize;
+    for i in 0..info.textures as usize {
+        let texture = reader.u64(textures + i * 8)? as usize;
+        reader.magic(texture, b"BRTI")?;
+    }
+    Some(info)
+}
+
+const NX_MODEL_ARRAY: usize = 0x28;
+const NX_COUNTS: usize = 0xbc;
+const NX_MODEL_SIZE: usize = 0x78;
+
+fn read_info_nx(bytes: &[u8]) -> Option<BfresInfo> {
+    let reader = Reader {
+        bytes,
+        endian: bom(bytes, 0xc)?,
+    };
+    let count = |index: usize| reader.u16(NX_COUNTS + index * 2).map(u32::from);
+    let mut info = BfresInfo {
+        models: count(0)?,
+        embedded: count(6)?,
+        ..Default::default()
+    };
+
+    if info.models > 0 {
+        let array = reader.u64(NX_MODEL_ARRAY)? as usize;
+        for i in 0..info.models as usize {
+            let model = array + i * NX_MODEL_SIZE;
+            reader.magic(model, b"FMDL")?;
+            info.shapes += reader.u16(model + 0x6a)? as u32;
+            info.materials += reader.u16(model + 0x6c)? as u32;
+        }
+    }
+    Some(info)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn read_wiiu_bfres() {
+        let bytes = std::fs::read("test/DgnMrgPrt_Dungeon061.sbfres").unwrap();
+        let info = super::read_info(&super::decompress::yaz0(&bytes).unwrap()).unwrap();
+        assert_eq!(info, super::BfresInfo {
+            models: 1,
+            shapes: 39,
+            materials: 39,
+            bones: 1,
+            embedded: 1,
+        });
+    }
+
+    /// Files unlike the fixtures the costs were measured on are left to the
+    /// size heuristic.
+    #[test]
+    fn unfitted_bfres() {
+        use crate::Platform;
+
+        let bytes = std::fs::read("test/FldObj_TreeRootTropical_A_Far.sbfres").unwrap();
+        let bytes = super::decompress::yaz0(&bytes).unwrap().into_owned();
+        assert!(super::parse_size(&bytes, Platform::WiiU).is_some());
+        assert_eq!(super::parse_size(&bytes, Platform::Switch), None);
+        // A second embedded file
+        let mut extra_file = bytes.clone();
+        extra_file[super::WIIU_COUNTS + super::WIIU_EMBEDDED_GROUP * 2 + 1] = 2;
+        assert_eq!(super::parse_size(&extra_file, Platform::WiiU), None);
+        // No models, and so no shapes
+        let mut no_models = bytes;
+        no_models[super::WIIU_COUNTS + super::WIIU_MODEL_GROUP * 2 + 1] = 0;
+        assert_eq!(super::parse_size(&no_models, Platform::WiiU), None);
+    }
+
+    #[test]
+    fn read_wiiu_textures() {
+        let bytes = std::fs::read("test/Animal_Bass.Tex1.sbfres").unwrap();
+        let info = super::read_textures(&super::decompress::yaz0(&bytes).unwrap()).unwrap();
+        assert_eq!(info.textures, 8);
+    }
+}
@@ -0,0 +1,490 @@
+//! Re-fits the file size based estimation heuristics against known values.
+//!
+//! The multiplier tables used for estimates (as in `estimate_bfres` and
+//! `estimate_aamp`) map ranges of file sizes to a multiplier. A [`Calibrator`]
+//! takes a corpus of resources with their true RSTB values, usually taken
+//! from a game dump and its stock table, and for each resource type fits a
+//! new table that does not underestimate any value in the corpus. It also
+//! reports how far off the current estimates are, so changes can be checked
+//! before the new tables are pasted into the crate.
+//!
+//! Tables are fitted in the form their estimator uses them: BFRES tables
+//! look up the file size itself, while the AAMP tables look up the size
+//! scaled by 1.05 (twice for `bas`) and have Switch results scaled by 1.5
+//! afterwards. Types estimated by a formula rather than a table, such as
+//! `bassetting`, `bdmgparam` or `bphysics`, are not fitted.
+//!
+//! ```
+//! use rstb::{calc::calibrate::Calibrator, Platform};
+//!
+//! let mut calibrator = Calibrator::new(Platform::WiiU);
+//! calibrator.add_size("Model/Link.bfres", 1000, 4000);
+//! calibrator.add_size("Model/Zelda.bfres", 2000, 7000);
+//! for result in calibrator.fit() {
+//!     assert_eq!(result.fitted.under, 0);
+//!     assert_eq!(result.estimate(2000), 7000);
+//!     println!("{}\n{}", result.ext, result.to_rust());
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use super::{data_size, estimate_from_bytes_and_name, estimate_from_size_and_name, resource_ext};
+use crate::Platform;
+
+const DEFAULT_BUCKETS: usize = 8;
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    filesize: usize,
+    value:    u32,
+    current:  Option<u32>,
+}
+
+/// Collects (resource, true value) pairs and fits estimation tables for them.
+#[derive(Debug, Clone)]
+pub struct Calibrator {
+    platform: Platform,
+    buckets:  usize,
+    samples:  BTreeMap<String, Vec<Sample>>,
+}
+
+/// Summary of how an estimate compares to the true values of a corpus.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ErrorStats {
+    /// Number of samples compared.
+    pub samples: usize,
+    /// Number of samples for which no estimate could be made.
+    pub unsupported: usize,
+    /// Number of underestimated samples. These are the ones that crash the
+    /// game.
+    pub under: usize,
+    /// Largest underestimate, in bytes.
+    pub max_under: u32,
+    /// Mean underestimate over the underestimated samples, in bytes.
+    pub mean_under: f64,
+    /// Number of overestimated samples.
+    pub over: usize,
+    /// Largest overestimate, in bytes.
+    pub max_over: u32,
+    /// Mean overestimate over the overestimated samples, in bytes.
+    pub mean_over: f64,
+}
+
+/// One row of a fitted multiplier table, covering file sizes from `start` up
+/// to (but not including) `end`, or all larger sizes if `end` is `None`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bucket {
+    pub start: usize,
+    pub end: Option<usize>,
+    pub multiplier: f32,
+}
+
+/// The calibration result for one resource type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeCalibration {
+    /// The resource type, as used in calculations (e.g. `bfres`, `baiprog`).
+    pub ext: String,
+    /// How the crate's current estimates compare to the corpus.
+    pub current: ErrorStats,
+    /// How the fitted table compares to the corpus.
+    pub fitted: ErrorStats,
+    /// The fitted multiplier table, ordered by the size its estimator looks
+    /// up.
+    pub table: Vec<Bucket>,
+    form: Form,
+    platform: Platform,
+}
+
+/// How an estimator applies its multiplier table to a file size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Form {
+    /// `filesize as f32 * multiplier`, as in `estimate_bfres`.
+    Bfres,
+    /// The file size is scaled by 1.05, twice for `bas`, before the lookup,
+    /// and Switch results by 1.5 after it, as in `estimate_aamp`.
+    Aamp { bas: bool },
+}
+
+impl Form {
+    fn of(ext: &str) -> Option<Self> {
+        match ext {
+            "bfres" => Some(Self::Bfres),
+            "baiprog" | "bas" | "baslist" | "bdrop" | "bgparamlist" | "brecipe" | "bshop"
+            | "bxml" => Some(Self::Aamp { bas: ext == "bas" }),
+            _ => None,
+        }
+    }
+
+    fn size(self, filesize: usize) -> f32 {
+        match self {
+            Self::Bfres => filesize as f32,
+            Self::Aamp { bas } => {
+                let mut size = filesize as f32 * 1.05;
+                if bas {
+                    size *= 1.05;
+                }
+                size
+            }
+        }
+    }
+
+    fn platform_scale(self, platform: Platform) -> f32 {
+        match (self, platform) {
+            (Self::Aamp { .. }, Platform::Switch) => 1.5,
+            _ => 1.0,
+        }
+    }
+
+    fn apply(self, filesize: usize, multiplier: f32, platform: Platform) -> u32 {
+        (self.size(filesize) * multiplier * self.platform_scale(platform)) as u32
+    }
+
+    fn scrutinee(self) -> &'static str {
+        match self {
+            Self::Bfres => "filesize",
+            Self::Aamp { .. } => "size as usize",
+        }
+    }
+}
+
+impl ErrorStats {
+    pub(crate) fn from_pairs(pairs: impl Iterator<Item = (Option<u32>, u32)>) -> Self {
+        let mut stats = Self::default();
+        let (mut total_under, mut total_over) = (0u64, 0u64);
+        for (estimate, value) in pairs {
+            stats.samples += 1;
+            match estimate {
+                None => stats.unsupported += 1,
+                Some(estimate) if estimate < value => {
+                    let diff = value - estimate;
+                    stats.under += 1;
+                    stats.max_under = stats.max_under.max(diff);
+                    total_under += diff as u64;
+                }
+                Some(estimate) if estimate > value => {
+                    let diff = estimate - value;
+                    stats.over += 1;
+                    stats.max_over = stats.max_over.max(diff);
+                    total_over += diff as u64;
+                }
+                Some(_) => (),
+            }
+        }
+        if stats.under > 0 {
+            stats.mean_under = total_under as f64 / stats.under as f64;
+        }
+        if stats.over > 0 {
+            stats.mean_over = total_over as f64 / stats.over as f64;
+        }
+        stats
+    }
+}
+
+impl TypeCalibration {
+    /// Estimates an RSTB value for a file size using the fitted table, the
+    /// same way the type's estimator would.
+    pub fn estimate(&self, filesize: usize) -> u32 {
+        let size = self.form.size(filesize) as usize;
+        let multiplier = self
+            .table
+            .iter()
+            .find(|bucket| bucket.end.map(|end| size < end).unwrap_or(true))
+            .map(|bucket| bucket.multiplier)
+            .unwrap_or(1.0);
+        self.form.apply(filesize, multiplier, self.platform)
+    }
+
+    /// Formats the fitted table as the `match` the type's estimator uses, on
+    /// `filesize` for BFRES and on `size as usize` for AAMP types, so it can
+    /// be pasted over the current one.
+    pub fn to_rust(&self) -> String {
+        let mut out = format!("match {} {{\n", self.form.scrutinee());
+        for bucket in &self.table {
+            match bucket.end {
+                Some(end) => {
+                    out.push_str(&format!(
+                        "    ({}..{}) => {:?},\n",
+                        literal(bucket.start),
+                        literal(end),
+                        bucket.multiplier
+                    ))
+                }
+                None => out.push_str(&format!("    _ => {:?},\n", bucket.multiplier)),
+            }
+        }
+        out.push('}');
+        out
+    }
+}
+
+/// Formats an integer literal with digit separators, as the crate's tables
+/// are written.
+fn literal(value: usize) -> String {
+    let digits = value.to_string();
+    let mut out = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push('_');
+        }
+        out.push(digit);
+    }
+    out
+}
+
+impl Calibrator {
+    /// Creates an empty calibrator for the given platform.
+    pub fn new(platform: Platform) -> Self {
+        Self {
+            platform,
+            buckets: DEFAULT_BUCKETS,
+            samples: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the maximum number of file size ranges in each fitted table.
+    /// Defaults to 8.
+    pub fn set_buckets(&mut self, buckets: usize) {
+        self.buckets = buckets.max(1);
+    }
+
+    /// Adds a resource and its true RSTB value to the corpus. The bytes may
+    /// be Yaz0 compressed.
+    pub fn add(&mut self, name: &str, bytes: &[u8], value: u32) {
+        let filesize = data_size(bytes);
+        let current = if bytes.len() < 8 {
+            None
+        } else {
+            estimate_from_bytes_and_name(bytes, name, self.platform)
+        };
+        self.push(name, filesize, value, current);
+    }
+
+    /// Adds a resource's uncompressed size and its true RSTB value to the
+    /// corpus.
+    pub fn add_size(&mut self, name: &str, filesize: usize, value: u32) {
+        let current = estimate_from_size_and_name(filesize, name, self.platform);
+        self.push(name, filesize, value, current);
+    }
+
+    fn push(&mut self, name: &str, filesize: usize, value: u32, current: Option<u32>) {
+        if let Some(ext) = resource_ext(name) {
+            self.samples
+                .entry(ext.to_owned())
+                .or_default()
+                .push(Sample {
+                    filesize,
+                    value,
+                    current,
+                });
+        }
+    }
+
+    /// Fits a table for each resource type in the corpus whose estimator
+    /// uses one.
+    pub fn fit(&self) -> Vec<TypeCalibration> {
+        self.samples
+            .iter()
+            .filter_map(|(ext, samples)| {
+                let form = Form::of(ext)?;
+                let mut sorted = samples.clone();
+                sorted.sort_by_key(|sample| sample.filesize);
+                let mut result = TypeCalibration {
+                    ext: ext.clone(),
+                    current: ErrorStats::from_pairs(
+                        sorted.iter().map(|sample| (sample.current, sample.value)),
+                    ),
+                    fitted: ErrorStats::default(),
+                    table: fit_table(&sorted, self.buckets, form, self.platform),
+                    form,
+                    platform: self.platform,
+                };
+                result.fitted = ErrorStats::from_pairs(
+                    sorted
+                        .iter()
+                        .map(|sample| (Some(result.estimate(sample.filesize)), sample.value)),
+                );
+                Some(result)
+            })
+            .collect()
+    }
+}
+
+/// Splits samples sorted by file size into ranges of roughly equal count and
+/// takes the largest multiplier any sample in each needs, so no sample is
+/// underestimated.
+fn fit_table(sorted: &[Sample], buckets: usize, form: Form, platform: Platform) -> Vec<Bucket> {
+    let size = |sample: &Sample| form.size(sample.filesize) as usize;
+    let target = sorted.len().div_ceil(buckets).max(1);
+    let mut groups: Vec<&[Sample]> = vec![];
+    let mut start = 0;
+    for i in 1..=sorted.len() {
+        let at_end = i == sorted.len();
+        // Never split samples of the same size across two ranges
+        if at_end || (i - start >= target && size(&sorted[i]) != size(&sorted[i - 1])) {
+            groups.push(&sorted[start..i]);
+            start = i;
+        }
+    }
+    let mut table: Vec<Bucket> = groups
+        .iter()
+        .map(|group| {
+            let scale = form.platform_scale(platform) as f64;
+            let ratio = group
+                .iter()
+                .map(|sample| {
+                    sample.value as f64 / (form.size(sample.filesize) as f64 * scale).max(1.0)
+                })
+                .fold(0.0, f64::max);
+            let mut thousandths = (ratio * 1000.0).ceil() as u32;
+            // The estimators truncate, so float rounding can still leave a
+            // sample a byte short
+            while group.iter().any(|sample| {
+                form.apply(sample.filesize, thousandths as f32 / 1000.0, platform) < sample.value
+            }) {
+                thousandths += 1;
+            }
+            Bucket {
+                start: size(&group[0]),
+                end: None,
+                multiplier: thousandths as f32 / 1000.0,
+            }
+        })
+        .collect();
+    let starts: Vec<usize> = table.iter().skip(1).map(|bucket| bucket.start).collect();
+    for (bucket, end) in table.iter_mut().zip(starts) {
+        bucket.end = Some(end);
+    }
+    if let Some(first) = table.first_mut() {
+        first.start = 0;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_never_underestimates() {
+        let mut calibrator = Calibrator::new(Platform::WiiU);
+        calibrator.set_buckets(3);
+        for i in 1..=30usize {
+            let size = i * 1000;
+            calibrator.add_size("Model/Test.bfres", size, (size * (3 + i % 4)) as u32 / 2);
+        }
+        let results = calibrator.fit();
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert_eq!(result.ext, "bfres");
+        assert_eq!(result.table.len(), 3);
+        assert_eq!(result.table[0].start, 0);
+        assert_eq!(result.table[2].end, None);
+        assert_eq!(result.fitted.samples, 30);
+        assert_eq!(result.fitted.under, 0);
+        assert!(result
+            .to_rust()
+            .starts_with("match filesize {\n    (0..11_000) =>"));
+    }
+
+    /// Reads a table written by [`TypeCalibration::to_rust`] back in.
+    fn parse_table(code: &str) -> Vec<Bucket> {
+        let number = |text: &str| text.replace('_', "").parse::<usize>().unwrap();
+        code.lines()
+            .filter_map(|line| line.trim().strip_suffix(','))
+            .map(|line| {
+                let (range, multiplier) = line.split_once(" => ").unwrap();
+                let (start, end) = match range.trim_matches(['(', ')']).split_once("..") {
+                    Some((start, end)) => (number(start), Some(number(end))),
+                    None => (0, None),
+                };
+                Bucket {
+                    start,
+                    end,
+                    multiplier: multiplier.parse().unwrap(),
+                }
+            })
+            .collect()
+    }
+
+    /// Finds the table following each of `markers` in turn in the crate's
+    /// estimators.
+    fn crate_table(markers: &[&str]) -> &'static str {
+        let source = include_str!("mod.rs");
+        let from = markers.iter().fold(0, |from, marker| {
+            from + source[from..].find(marker).unwrap() + marker.len()
+        });
+        let start = from + source[from..].find("match ").unwrap();
+        let end = start + source[start..].find('}').unwrap() + 1;
+        &source[start..end]
+    }
+
+    #[test]
+    fn round_trip() {
+        let squash = |code: &str| code.split_whitespace().collect::<String>();
+        for (markers, name, platforms) in [
+            (
+                &["fn estimate_bfres", "Platform::WiiU"][..],
+                "Model/Test.bfres",
+                &[Platform::WiiU][..],
+            ),
+            (
+                &["fn estimate_bfres", "Platform::Switch"],
+                "Model/Test.bfres",
+                &[Platform::Switch],
+            ),
+            (
+                &["fn estimate_aamp", "\"bas\" => {"],
+                "Actor/AS/Test.bas",
+                &[Platform::WiiU, Platform::Switch],
+            ),
+            (
+                &["fn estimate_aamp", "\"bxml\" => {"],
+                "Actor/ActorLink/Test.bxml",
+                &[Platform::WiiU, Platform::Switch],
+            ),
+        ] {
+            let code = crate_table(markers);
+            let ext = resource_ext(name).unwrap();
+            for &platform in platforms {
+                // The crate's own table comes back out as the same code and
+                // gives the same values as the estimator it was taken from
+                let current = TypeCalibration {
+                    ext: ext.to_owned(),
+                    current: ErrorStats::default(),
+                    fitted: ErrorStats::default(),
+                    table: parse_table(code),
+                    form: Form::of(ext).unwrap(),
+                    platform,
+                };
+                assert_eq!(squash(&current.to_rust()), squash(code), "{name}");
+                let mut calibrator = Calibrator::new(platform);
+                for filesize in (16..20_000).step_by(7) {
+                    let value = estimate_from_size_and_name(filesize, name, platform).unwrap();
+                    assert_eq!(current.estimate(filesize), value, "{name} {filesize}");
+                    calibrator.add_size(name, filesize, value);
+                }
+
+                // A table fitted to those values and written out reproduces
+                // the values fitted to
+                let fitted = &calibrator.fit()[0];
+                assert_eq!(fitted.fitted.under, 0);
+                let pasted = TypeCalibration {
+                    table: parse_table(&fitted.to_rust()),
+                    ..fitted.clone()
+                };
+                for filesize in (16..20_000).step_by(7) {
+                    assert_eq!(pasted.estimate(filesize), fitted.estimate(filesize));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn skip_formulas() {
+        let mut calibrator = Calibrator::new(Platform::WiiU);
+        calibrator.add_size("Actor/Physics/Test.bphysics", 1000, 4000);
+        calibrator.add_size("Actor/AS/Test.bassetting", 1000, 4000);
+        assert!(calibrator.fit().is_empty());
+    }
+}
@@ -5,26 +5,33 @@ use roead::aamp::{ParameterIO, ParameterList, ParameterObject};
 use super::cpp_classes::{
     agl::Parameter, AIProgram::*, Bool32, SafeString, Vector3f, F32, S32, U32,
 };
-use crate::Endian;
+use crate::Platform;
 
 const CLASS_SIZE_WIIU: u32 = 0x30c;
 const CLASS_SIZE_NX: u32 = 0x448;
 
 const BAIPROG_OVERHEAD: u32 = 0xe6;
 
-pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
-    let mut total_size = match endian {
-        Endian::Big => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
-        Endian::Little => super::PARSE_CONST_NX + CLASS_SIZE_NX,
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX,
     };
     total_size += BAIPROG_OVERHEAD;
-    let a = ParameterIO::from_binary(bytes).ok()?;
 
     let ai = a.param_root.lists.get("AI")?;
     let num_ai = ai.lists.len() as u32;
     if num_ai > 0 {
         for i in 0..num_ai {
-            parse_aiaction(ai.lists.get(format!("AI_{}", i))?, &mut total_size, endian);
+            parse_aiaction(
+                ai.lists.get(format!("AI_{}", i))?,
+                &mut total_size,
+                platform,
+            );
         }
     }
     let action = a.param_root.lists.get("Action")?;
@@ -34,7 +41,7 @@ pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
             parse_aiaction(
                 action.lists.get(format!("Action_{}", i))?,
                 &mut total_size,
-                endian,
+                platform,
             );
         }
     }
@@ -45,7 +52,7 @@ pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
                 parse_behavior(
                     behavior.lists.get(format!("Behavior_{}", i))?,
                     &mut total_size,
-                    endian,
+                    platform,
                 );
             }
         }
@@ -57,7 +64,7 @@ pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
                 parse_query(
                     query.lists.get(format!("Query_{}", i))?,
                     &mut total_size,
-                    endian,
+                    platform,
                 );
             }
         }
@@ -82,10 +89,10 @@ fn parse_behavioridx(obj: &ParameterObject, size: &mut u32) {
     *size += num * size_of::<u8>() as u32;
 }
 
-fn parse_aiaction(list: &ParameterList, size: &mut u32, endian: Endian) {
-    let aiactiondef_size = match endian {
-        Endian::Big => size_of::<AIActionDef<u32>>() as u32,
-        Endian::Little => size_of::<AIActionDef<u64>>() as u32,
+fn parse_aiaction(list: &ParameterList, size: &mut u32, platform: Platform) {
+    let aiactiondef_size = match platform {
+        Platform::WiiU => size_of::<AIActionDef<u32>>() as u32,
+        Platform::Switch => size_of::<AIActionDef<u64>>() as u32,
     };
     *size += aiactiondef_size;
 
@@ -98,72 +105,72 @@ fn parse_aiaction(list: &ParameterList, size: &mut u32, endian: Endian) {
     }
 
     if let Some(sinst_obj) = list.objects.get("SInst") {
-        parse_defparams(sinst_obj, size, endian);
+        parse_defparams(sinst_obj, size, platform);
     }
 }
 
-fn parse_behavior(list: &ParameterList, size: &mut u32, endian: Endian) {
-    let behaviordef_size = match endian {
-        Endian::Big => size_of::<BehaviorDef<u32>>() as u32,
-        Endian::Little => size_of::<BehaviorDef<u64>>() as u32,
+fn parse_behavior(list: &ParameterList, size: &mut u32, platform: Platform) {
+    let behaviordef_size = match platform {
+        Platform::WiiU => size_of::<BehaviorDef<u32>>() as u32,
+        Platform::Switch => size_of::<BehaviorDef<u64>>() as u32,
     };
     *size += behaviordef_size;
 
     if let Some(sinst_obj) = list.objects.get("SInst") {
-        parse_defparams(sinst_obj, size, endian);
+        parse_defparams(sinst_obj, size, platform);
     }
 }
 
-fn parse_query(list: &ParameterList, size: &mut u32, endian: Endian) {
-    let querydef_size = match endian {
-        Endian::Big => size_of::<QueryDef<u32>>() as u32,
-        Endian::Little => size_of::<QueryDef<u64>>() as u32,
+fn parse_query(list: &ParameterList, size: &mut u32, platform: Platform) {
+    let querydef_size = match platform {
+        Platform::WiiU => size_of::<QueryDef<u32>>() as u32,
+        Platform::Switch => size_of::<QueryDef<u64>>() as u32,
     };
     *size += querydef_size;
 
     if let Some(sinst_obj) = list.objects.get("SInst") {
-        parse_defparams(sinst_obj, size, endian);
+        parse_defparams(sinst_obj, size, platform);
     }
 }
 
-fn parse_defparams(obj: &ParameterObject, size: &mut u32, endian: Endian) {
+fn parse_defparams(obj: &ParameterObject, size: &mut u32, platform: Platform) {
     let sinst_num_params = obj.len() as u32;
     if sinst_num_params > 0 {
-        let ptr_size: u32 = match endian {
-            Endian::Big => size_of::<u32>() as u32,
-            Endian::Little => size_of::<u64>() as u32,
+        let ptr_size: u32 = match platform {
+            Platform::WiiU => size_of::<u32>() as u32,
+            Platform::Switch => size_of::<u64>() as u32,
         };
         *size += sinst_num_params * ptr_size;
         for (_, p) in obj.iter() {
             if p.as_bool().is_ok() {
-                *size += match endian {
-                    Endian::Big => size_of::<Parameter<u32, Bool32>>() as u32,
-                    Endian::Little => size_of::<Parameter<u64, Bool32>>() as u32,
+                *size += match platform {
+                    Platform::WiiU => size_of::<Parameter<u32, Bool32>>() as u32,
+                    Platform::Switch => size_of::<Parameter<u64, Bool32>>() as u32,
                 };
             } else if p.as_u32().is_ok() {
-                *size += match endian {
-                    Endian::Big => size_of::<Parameter<u32, U32>>() as u32,
-                    Endian::Little => size_of::<Parameter<u64, U32>>() as u32,
+                *size += match platform {
+                    Platform::WiiU => size_of::<Parameter<u32, U32>>() as u32,
+                    Platform::Switch => size_of::<Parameter<u64, U32>>() as u32,
                 };
             } else if p.as_i32().is_ok() {
-                *size += match endian {
-                    Endian::Big => size_of::<Parameter<u32, S32>>() as u32,
-                    Endian::Little => size_of::<Parameter<u64, S32>>() as u32,
+                *size += match platform {
+                    Platform::WiiU => size_of::<Parameter<u32, S32>>() as u32,
+                    Platform::Switch => size_of::<Parameter<u64, S32>>() as u32,
                 };
             } else if p.as_f32().is_ok() {
-                *size += match endian {
-                    Endian::Big => size_of::<Parameter<u32, F32>>() as u32,
-                    Endian::Little => size_of::<Parameter<u64, F32>>() as u32,
+                *size += match platform {
+                    Platform::WiiU => size_of::<Parameter<u32, F32>>() as u32,
+                    Platform::Switch => size_of::<Parameter<u64, F32>>() as u32,
                 };
             } else if p.as_str().is_ok() {
-                *size += match endian {
-                    Endian::Big => size_of::<Parameter<u32, SafeString<u32>>>() as u32,
-                    Endian::Little => size_of::<Parameter<u64, SafeString<u64>>>() as u32,
+                *size += match platform {
+                    Platform::WiiU => size_of::<Parameter<u32, SafeString<u32>>>() as u32,
+                    Platform::Switch => size_of::<Parameter<u64, SafeString<u64>>>() as u32,
                 };
             } else if p.as_vec3().is_ok() {
-                *size += match endian {
-                    Endian::Big => size_of::<Parameter<u32, Vector3f>>() as u32,
-                    Endian::Little => size_of::<Parameter<u64, Vector3f>>() as u32,
+                *size += match platform {
+                    Platform::WiiU => size_of::<Parameter<u32, Vector3f>>() as u32,
+                    Platform::Switch => size_of::<Parameter<u64, Vector3f>>() as u32,
                 };
             }
         }
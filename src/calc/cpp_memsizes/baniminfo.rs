@@ -0,0 +1,94 @@
+use std::mem::size_of;
+
+use roead::aamp::{Parameter as AampParameter, ParameterIO};
+
+use super::cpp_classes::{
+    agl::Parameter, AnimationInfo::*, Bool32, Color4f, Curve, FixedSafeString256,
+    FixedSafeString32, FixedSafeString64, Quatf, SafeString, SeadBuffer, Vector2f, Vector3f,
+    Vector4f, F32, S32, U32,
+};
+use crate::Platform;
+
+const CLASS_SIZE_WIIU: u32 = 0x24c;
+const CLASS_SIZE_NX: u32 = 0x2c8;
+
+const BANIMINFO_OVERHEAD_WIIU: u32 = 0x64;
+const BANIMINFO_OVERHEAD_NX: u32 = 0x58;
+
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU + BANIMINFO_OVERHEAD_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX + BANIMINFO_OVERHEAD_NX,
+    };
+    let (animinfo_size, animparamobj_size, ptr_size): (u32, u32, u32);
+    match platform {
+        Platform::WiiU => {
+            animinfo_size = size_of::<AnimInfo<u32>>() as u32;
+            animparamobj_size = size_of::<AnimParamObj<u32>>() as u32;
+            ptr_size = size_of::<u32>() as u32;
+        }
+        Platform::Switch => {
+            animinfo_size = size_of::<AnimInfo<u64>>() as u32;
+            animparamobj_size = size_of::<AnimParamObj<u64>>() as u32;
+            ptr_size = size_of::<u64>() as u32;
+        }
+    }
+
+    // Each top-level list is the info for a single animation
+    total_size =
+        total_size.checked_add(super::array_size(a.param_root.lists.len(), animinfo_size)?)?;
+    for (_, anim) in a.param_root.lists.iter() {
+        total_size =
+            total_size.checked_add(super::array_size(anim.objects.len(), animparamobj_size)?)?;
+        for (_, obj) in anim.objects.iter() {
+            total_size = total_size.checked_add(super::array_size(obj.len(), ptr_size)?)?;
+            for (_, param) in obj.iter() {
+                total_size = total_size.checked_add(match platform {
+                    Platform::WiiU => param_size::<u32>(param)?,
+                    Platform::Switch => param_size::<u64>(param)?,
+                })?;
+            }
+        }
+    }
+
+    Some(total_size)
+}
+
+/// The size of the `agl::utl::Parameter` made for a parameter, with `T` as the
+/// pointer type. Buffers also allocate their data separately.
+fn param_size<T>(param: &AampParameter) -> Option<u32> {
+    fn size<U>() -> u32 {
+        size_of::<U>() as u32
+    }
+
+    let buffer = |len: usize, item_size: u32| {
+        super::array_size(len, item_size)?.checked_add(size::<Parameter<T, SeadBuffer<T>>>())
+    };
+    Some(match param {
+        AampParameter::Bool(_) => size::<Parameter<T, Bool32>>(),
+        AampParameter::F32(_) => size::<Parameter<T, F32>>(),
+        AampParameter::I32(_) => size::<Parameter<T, S32>>(),
+        AampParameter::U32(_) => size::<Parameter<T, U32>>(),
+        AampParameter::Vec2(_) => size::<Parameter<T, Vector2f>>(),
+        AampParameter::Vec3(_) => size::<Parameter<T, Vector3f>>(),
+        AampParameter::Vec4(_) => size::<Parameter<T, Vector4f>>(),
+        AampParameter::Color(_) => size::<Parameter<T, Color4f>>(),
+        AampParameter::Quat(_) => size::<Parameter<T, Quatf>>(),
+        AampParameter::String32(_) => size::<Parameter<T, FixedSafeString32<T>>>(),
+        AampParameter::String64(_) => size::<Parameter<T, FixedSafeString64<T>>>(),
+        AampParameter::String256(_) => size::<Parameter<T, FixedSafeString256<T>>>(),
+        AampParameter::StringRef(_) => size::<Parameter<T, SafeString<T>>>(),
+        AampParameter::Curve1(_) => size::<Parameter<T, [Curve<T>; 1]>>(),
+        AampParameter::Curve2(_) => size::<Parameter<T, [Curve<T>; 2]>>(),
+        AampParameter::Curve3(_) => size::<Parameter<T, [Curve<T>; 3]>>(),
+        AampParameter::Curve4(_) => size::<Parameter<T, [Curve<T>; 4]>>(),
+        AampParameter::BufferInt(v) => buffer(v.len(), size::<i32>())?,
+        AampParameter::BufferU32(v) => buffer(v.len(), size::<u32>())?,
+        AampParameter::BufferF32(v) => buffer(v.len(), size::<f32>())?,
+        AampParameter::BufferBinary(v) => buffer(v.len(), size::<u8>())?,
+    })
+}
@@ -3,20 +3,23 @@ use std::mem::size_of;
 use roead::aamp::ParameterIO;
 
 use super::cpp_classes::ASList::*;
-use crate::Endian;
+use crate::Platform;
 
 const CLASS_SIZE_WIIU: u32 = 0x2f4;
 const CLASS_SIZE_NX: u32 = 0x410;
 
 const BASLIST_OVERHEAD: u32 = 0x80;
 
-pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
-    let mut total_size = match endian {
-        Endian::Big => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
-        Endian::Little => super::PARSE_CONST_NX + CLASS_SIZE_NX,
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX,
     };
     total_size += BASLIST_OVERHEAD;
-    let a = ParameterIO::from_binary(bytes).ok()?;
     let (asdefine_size, cfdefine_size, cfpost_size, cfexcept_size, addres_size): (
         u32,
         u32,
@@ -24,15 +27,15 @@ pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
         u32,
         u32,
     );
-    match endian {
-        Endian::Big => {
+    match platform {
+        Platform::WiiU => {
             asdefine_size = size_of::<ASDefine<u32>>() as u32;
             cfdefine_size = size_of::<CFDefine<u32>>() as u32;
             cfpost_size = size_of::<CFPost<u32>>() as u32;
             cfexcept_size = size_of::<CFExcept<u32>>() as u32;
             addres_size = size_of::<AddRes<u32>>() as u32;
         }
-        Endian::Little => {
+        Platform::Switch => {
             asdefine_size = size_of::<ASDefine<u64>>() as u32;
             cfdefine_size = size_of::<CFDefine<u64>>() as u32;
             cfpost_size = size_of::<CFPost<u64>>() as u32;
@@ -0,0 +1,56 @@
+use std::mem::size_of;
+
+use roead::aamp::ParameterIO;
+
+use super::cpp_classes::ASSetting::*;
+use crate::Platform;
+
+const CLASS_SIZE_WIIU: u32 = 0x1d8;
+const CLASS_SIZE_NX: u32 = 0x260;
+
+// Measured from the game's only ASSetting (101452 on Wii U, 165864 on Switch,
+// from 27960 bytes) less its rounded size, the parse constant and the class
+// size. With a single file the stock table can't split this from the cost of
+// its entries, so the stock file and edits of it come out above their actual
+// values by the cost of the stock entries, never below them. Taking that cost
+// out needs the stock file's blender bone and bone param counts, which no
+// fixture here provides.
+const BASSETTING_OVERHEAD_WIIU: u32 = 0x11c50;
+const BASSETTING_OVERHEAD_NX: u32 = 0x216e0;
+
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU + BASSETTING_OVERHEAD_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX + BASSETTING_OVERHEAD_NX,
+    };
+    let (blenderbone_size, boneparam_size): (u32, u32);
+    match platform {
+        Platform::WiiU => {
+            blenderbone_size = size_of::<BlenderBone<u32>>() as u32;
+            boneparam_size = size_of::<BoneParam<u32>>() as u32;
+        }
+        Platform::Switch => {
+            blenderbone_size = size_of::<BlenderBone<u64>>() as u32;
+            boneparam_size = size_of::<BoneParam<u64>>() as u32;
+        }
+    }
+
+    if let Some(blenderbone_list) = a.param_root.lists.get("BlenderBone") {
+        total_size = total_size.checked_add(super::array_size(
+            blenderbone_list.lists.len(),
+            blenderbone_size,
+        )?)?;
+        for (_, blenderbone) in blenderbone_list.lists.iter() {
+            if let Some(bones) = blenderbone.lists.get("BoneParam") {
+                total_size = total_size
+                    .checked_add(super::array_size(bones.objects.len(), boneparam_size)?)?;
+            }
+        }
+    }
+
+    Some(total_size)
+}
@@ -0,0 +1,45 @@
+use std::mem::size_of;
+
+use roead::aamp::ParameterIO;
+
+use super::cpp_classes::BoneControl::*;
+use crate::Platform;
+
+const CLASS_SIZE_WIIU: u32 = 0x564;
+const CLASS_SIZE_NX: u32 = 0x8d0;
+
+const BBONECTRL_OVERHEAD: u32 = 0x1a8;
+
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU + BBONECTRL_OVERHEAD,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX + BBONECTRL_OVERHEAD,
+    };
+    let (bonegroup_size, bone_size): (u32, u32);
+    match platform {
+        Platform::WiiU => {
+            bonegroup_size = size_of::<BoneGroup<u32>>() as u32;
+            bone_size = size_of::<Bone<u32>>() as u32;
+        }
+        Platform::Switch => {
+            bonegroup_size = size_of::<BoneGroup<u64>>() as u32;
+            bone_size = size_of::<Bone<u64>>() as u32;
+        }
+    }
+
+    if let Some(bonegroups) = a.param_root.lists.get("BoneGroups") {
+        total_size =
+            total_size.checked_add(super::array_size(bonegroups.lists.len(), bonegroup_size)?)?;
+        for (_, bonegroup) in bonegroups.lists.iter() {
+            if let Some(bones) = bonegroup.objects.get("Bones") {
+                total_size = total_size.checked_add(super::array_size(bones.len(), bone_size)?)?;
+            }
+        }
+    }
+
+    Some(total_size)
+}
@@ -4,7 +4,7 @@ use phf::{phf_map, Map};
 use roead::aamp::ParameterIO;
 
 use super::cpp_classes::GParamList::*;
-use crate::Endian;
+use crate::Platform;
 
 const CLASS_SIZE_WIIU: u32 = 0x248;
 const CLASS_SIZE_NX: u32 = 0x2c0;
@@ -173,16 +173,19 @@ static OBJ_SIZES_NX: Map<&'static str, u32> = phf_map! {
     "Zora" => size_of::<GParamListObjectZora<u64>>() as u32,
 };
 
-pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
-    let mut total_size = match endian {
-        Endian::Big => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
-        Endian::Little => super::PARSE_CONST_NX + CLASS_SIZE_NX,
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX,
     };
     total_size += BGPARAM_OVERHEAD;
-    let a = ParameterIO::from_binary(bytes).ok()?;
-    let obj_map: &Map<&'static str, u32> = match endian {
-        Endian::Big => &OBJ_SIZES_WIIU,
-        Endian::Little => &OBJ_SIZES_NX,
+    let obj_map: &Map<&'static str, u32> = match platform {
+        Platform::WiiU => &OBJ_SIZES_WIIU,
+        Platform::Switch => &OBJ_SIZES_NX,
     };
     for (name, size) in (*obj_map).into_iter() {
         if a.param_root.objects.get(*name).is_some() {
@@ -0,0 +1,66 @@
+use std::mem::size_of;
+
+use roead::aamp::ParameterIO;
+
+use super::cpp_classes::LifeCondition::*;
+use crate::Platform;
+
+// The class sizes include one inline `DisplayDistance` and one `YLimit`
+const CLASS_SIZE_WIIU: u32 = 0x35c;
+const CLASS_SIZE_NX: u32 = 0x4b0;
+
+const BLIFECONDITION_OVERHEAD_WIIU: u32 = 0xb0;
+const BLIFECONDITION_OVERHEAD_NX: u32 = 0x88;
+
+// Each of these holds one `Item###` parameter per entry, stored in the
+// corresponding `ConditionList` buffer
+const CONDITION_LISTS: [&str; 4] = [
+    "InvalidWeathers",
+    "InvalidTimes",
+    "DeleteWeathers",
+    "DeleteTimes",
+];
+
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU + BLIFECONDITION_OVERHEAD_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX + BLIFECONDITION_OVERHEAD_NX,
+    };
+    let (conditionitem_size, displaydistance_size, ylimit_size): (u32, u32, u32);
+    match platform {
+        Platform::WiiU => {
+            conditionitem_size = size_of::<ConditionItem<u32>>() as u32;
+            displaydistance_size = size_of::<DisplayDistance<u32>>() as u32;
+            ylimit_size = size_of::<YLimit<u32>>() as u32;
+        }
+        Platform::Switch => {
+            conditionitem_size = size_of::<ConditionItem<u64>>() as u32;
+            displaydistance_size = size_of::<DisplayDistance<u64>>() as u32;
+            ylimit_size = size_of::<YLimit<u64>>() as u32;
+        }
+    }
+
+    for list_name in CONDITION_LISTS {
+        if let Some(list) = a.param_root.objects.get(list_name) {
+            total_size =
+                total_size.checked_add(super::array_size(list.len(), conditionitem_size)?)?;
+        }
+    }
+    // The first display distance and Y limit are stored in the class itself,
+    // so they are already covered by its size. Only further entries are
+    // allocated separately.
+    if let Some(display_distance) = a.param_root.objects.get("DisplayDistance") {
+        let extra = display_distance.len().saturating_sub(1);
+        total_size = total_size.checked_add(super::array_size(extra, displaydistance_size)?)?;
+    }
+    if let Some(y_limit) = a.param_root.objects.get("YLimitAlgorithm") {
+        let extra = y_limit.len().saturating_sub(1);
+        total_size = total_size.checked_add(super::array_size(extra, ylimit_size)?)?;
+    }
+
+    Some(total_size)
+}
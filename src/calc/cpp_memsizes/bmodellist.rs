@@ -3,7 +3,7 @@ use std::mem::size_of;
 use roead::aamp::ParameterIO;
 
 use super::cpp_classes::ModelList::*;
-use crate::Endian;
+use crate::Platform;
 
 const CLASS_SIZE_WIIU: u32 = 0x508;
 const CLASS_SIZE_NX: u32 = 0x7d0;
@@ -11,22 +11,25 @@ const CLASS_SIZE_NX: u32 = 0x7d0;
 const BMODELLIST_OVERHEAD: u32 = 0x74;
 const NUM_UNIT_MAX: u32 = 8;
 
-pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
-    let mut total_size = match endian {
-        Endian::Big => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
-        Endian::Little => super::PARSE_CONST_NX + CLASS_SIZE_NX,
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX,
     };
     total_size += BMODELLIST_OVERHEAD;
-    let a = ParameterIO::from_binary(bytes).ok()?;
     let (anmtarget_size, modeldata_size, partial_size, unit_size): (u32, u32, u32, u32);
-    match endian {
-        Endian::Big => {
+    match platform {
+        Platform::WiiU => {
             anmtarget_size = size_of::<AnmTarget<u32>>() as u32;
             modeldata_size = size_of::<ModelData<u32>>() as u32;
             partial_size = size_of::<Partial<u32>>() as u32;
             unit_size = size_of::<Unit<u32>>() as u32;
         }
-        Endian::Little => {
+        Platform::Switch => {
             anmtarget_size = size_of::<AnmTarget<u64>>() as u32;
             modeldata_size = size_of::<ModelData<u64>>() as u32;
             partial_size = size_of::<Partial<u64>>() as u32;
@@ -7,23 +7,29 @@ use std::mem::size_of;
 use roead::aamp::ParameterIO;
 
 use super::cpp_classes::{agl::Parameter, Physics::*, Vector3f};
-use crate::Endian;
+use crate::Platform;
 
 const CLASS_SIZE_WIIU: u32 = 0x324;
 const CLASS_SIZE_NX: u32 = 0x470;
 
-const PARAMSET_OVERHEAD: u32 = 0xdc; // for sizeof(phys::ParamSet) - shouldn't be related, but works for all tested mod files
-//const PARAMSET_OVERHEAD: u32 = 0x70; // valid for all vanilla files, but breaks some mod files
-// for some reason const PARAMSET_OVERHEAD: u32 = 0x1a8; // perhaps overly cautious overhead. fall
-// back to this if 0xdc doesn't work
+// for sizeof(phys::ParamSet) - shouldn't be related, but works for all tested
+// mod files
+pub(crate) const PARAMSET_OVERHEAD: u32 = 0xdc;
+// valid for all vanilla files, but breaks some mod files
+pub(crate) const PARAMSET_OVERHEAD_TIGHT: u32 = 0x70;
+// perhaps overly cautious overhead, for when 0xdc doesn't work
+pub(crate) const PARAMSET_OVERHEAD_CONSERVATIVE: u32 = 0x1a8;
 
-pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
-    let mut total_size = match endian {
-        Endian::Big => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
-        Endian::Little => super::PARSE_CONST_NX + CLASS_SIZE_NX,
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX,
     };
     total_size += PARAMSET_OVERHEAD;
-    let a = ParameterIO::from_binary(bytes).ok()?;
     let (rigidbodysetparam_size, rigidbodyparam_size, shapeparamobj_size, vertex_size): (
         u32,
         u32,
@@ -47,8 +53,8 @@ pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
         u32,
         u32,
     );
-    match endian {
-        Endian::Big => {
+    match platform {
+        Platform::WiiU => {
             rigidbodysetparam_size = size_of::<RigidBodySetParam<u32>>() as u32;
             rigidbodyparam_size = size_of::<RigidBodyParam<u32>>() as u32;
             shapeparamobj_size = size_of::<ShapeParamObj<u32>>() as u32 + 0x30;
@@ -66,7 +72,7 @@ pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
             edgerigidbodyparam_size = size_of::<EdgeRigidBodyParam<u32>>() as u32;
             supportboneparam_size = size_of::<SupportBoneParam<u32>>() as u32;
         }
-        Endian::Little => {
+        Platform::Switch => {
             rigidbodysetparam_size = size_of::<RigidBodySetParam<u64>>() as u32;
             rigidbodyparam_size = size_of::<RigidBodyParam<u64>>() as u32;
             shapeparamobj_size = size_of::<ShapeParamObj<u64>>() as u32;
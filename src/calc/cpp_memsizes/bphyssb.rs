@@ -0,0 +1,52 @@
+use std::mem::size_of;
+
+use roead::aamp::ParameterIO;
+
+use super::cpp_classes::Physics::*;
+use crate::Platform;
+
+const CLASS_SIZE_WIIU: u32 = 0x384;
+const CLASS_SIZE_NX: u32 = 0x5b0;
+
+const BPHYSSB_OVERHEAD_WIIU: u32 = 0x650;
+const BPHYSSB_OVERHEAD_NX: u32 = 0x8d0;
+
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU + BPHYSSB_OVERHEAD_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX + BPHYSSB_OVERHEAD_NX,
+    };
+    let (supportboneparam_size, mainbone_size, supportbonedata_size): (u32, u32, u32);
+    match platform {
+        Platform::WiiU => {
+            supportboneparam_size = size_of::<SupportBoneParam<u32>>() as u32;
+            mainbone_size = size_of::<SupportBoneMainBone<u32>>() as u32;
+            supportbonedata_size = size_of::<SupportBoneData<u32>>() as u32;
+        }
+        Platform::Switch => {
+            supportboneparam_size = size_of::<SupportBoneParam<u64>>() as u32;
+            mainbone_size = size_of::<SupportBoneMainBone<u64>>() as u32;
+            supportbonedata_size = size_of::<SupportBoneData<u64>>() as u32;
+        }
+    }
+    // The resource keeps its own copy of the setup param that `bphysics`
+    // files point to
+    total_size = total_size.checked_add(supportboneparam_size)?;
+
+    // Each top-level list is a main bone, and each object within it
+    // describes one support bone driven by it
+    total_size =
+        total_size.checked_add(super::array_size(a.param_root.lists.len(), mainbone_size)?)?;
+    for (_, main_bone) in a.param_root.lists.iter() {
+        total_size = total_size.checked_add(super::array_size(
+            main_bone.objects.len(),
+            supportbonedata_size,
+        )?)?;
+    }
+
+    Some(total_size)
+}
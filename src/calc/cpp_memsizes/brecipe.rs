@@ -3,27 +3,30 @@ use std::mem::size_of;
 use roead::aamp::ParameterIO;
 
 use super::cpp_classes::Recipe::*;
-use crate::Endian;
+use crate::Platform;
 
 const CLASS_SIZE_WIIU: u32 = 0x27c;
 const CLASS_SIZE_NX: u32 = 0x320;
 
 const BRECIPE_OVERHEAD: u32 = 0x58;
 
-pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
-    let mut total_size = match endian {
-        Endian::Big => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
-        Endian::Little => super::PARSE_CONST_NX + CLASS_SIZE_NX,
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX,
     };
     total_size += BRECIPE_OVERHEAD;
-    let a = ParameterIO::from_binary(bytes).ok()?;
     let (table_size, item_size): (u32, u32);
-    match endian {
-        Endian::Big => {
+    match platform {
+        Platform::WiiU => {
             table_size = size_of::<Table<u32>>() as u32;
             item_size = size_of::<Item<u32>>() as u32;
         }
-        Endian::Little => {
+        Platform::Switch => {
             table_size = size_of::<Table<u64>>() as u32;
             item_size = size_of::<Item<u64>>() as u32;
         }
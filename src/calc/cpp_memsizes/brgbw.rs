@@ -0,0 +1,49 @@
+use std::mem::size_of;
+
+use roead::aamp::ParameterIO;
+
+use super::cpp_classes::RagdollBlendWeight::*;
+use crate::Platform;
+
+const CLASS_SIZE_WIIU: u32 = 0x248;
+const CLASS_SIZE_NX: u32 = 0x2c0;
+
+const BRGBW_OVERHEAD_WIIU: u32 = 0x4;
+const BRGBW_OVERHEAD_NX: u32 = 0x18;
+
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU + BRGBW_OVERHEAD_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX + BRGBW_OVERHEAD_NX,
+    };
+    let (state_size, inputweight_size): (u32, u32);
+    match platform {
+        Platform::WiiU => {
+            state_size = size_of::<State<u32>>() as u32;
+            inputweight_size = size_of::<InputWeight<u32>>() as u32;
+        }
+        Platform::Switch => {
+            state_size = size_of::<State<u64>>() as u32;
+            inputweight_size = size_of::<InputWeight<u64>>() as u32;
+        }
+    }
+
+    total_size =
+        total_size.checked_add(super::array_size(a.param_root.lists.len(), state_size)?)?;
+    for (_, state) in a.param_root.lists.iter() {
+        // Every object in a state besides its `Setting` header is one input
+        // weight entry
+        let num_inputs = state
+            .objects
+            .iter()
+            .filter(|(name, _)| **name != "Setting".into())
+            .count();
+        total_size = total_size.checked_add(super::array_size(num_inputs, inputweight_size)?)?;
+    }
+
+    Some(total_size)
+}
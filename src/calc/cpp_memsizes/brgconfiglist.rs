@@ -0,0 +1,47 @@
+use std::mem::size_of;
+
+use roead::aamp::ParameterIO;
+
+use super::cpp_classes::RagdollConfigList::*;
+use crate::Platform;
+
+const CLASS_SIZE_WIIU: u32 = 0x2d4;
+const CLASS_SIZE_NX: u32 = 0x3d0;
+
+const BRGCONFIGLIST_OVERHEAD_WIIU: u32 = 0x260;
+const BRGCONFIGLIST_OVERHEAD_NX: u32 = 0x450;
+
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
+
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let mut total_size = match platform {
+        Platform::WiiU => super::PARSE_CONST_WIIU + CLASS_SIZE_WIIU + BRGCONFIGLIST_OVERHEAD_WIIU,
+        Platform::Switch => super::PARSE_CONST_NX + CLASS_SIZE_NX + BRGCONFIGLIST_OVERHEAD_NX,
+    };
+    let (impulseparam_size, bodyparam_size): (u32, u32);
+    match platform {
+        Platform::WiiU => {
+            impulseparam_size = size_of::<ImpulseParam<u32>>() as u32;
+            bodyparam_size = size_of::<BodyParam<u32>>() as u32;
+        }
+        Platform::Switch => {
+            impulseparam_size = size_of::<ImpulseParam<u64>>() as u32;
+            bodyparam_size = size_of::<BodyParam<u64>>() as u32;
+        }
+    }
+
+    if let Some(impulse_list) = a.param_root.lists.get("ImpulseParamList") {
+        total_size = total_size.checked_add(super::array_size(
+            impulse_list.objects.len(),
+            impulseparam_size,
+        )?)?;
+    }
+    if let Some(body_list) = a.param_root.lists.get("BodyParamList") {
+        total_size =
+            total_size.checked_add(super::array_size(body_list.objects.len(), bodyparam_size)?)?;
+    }
+
+    Some(total_size)
+}
@@ -1,24 +1,86 @@
+use std::mem::size_of;
+
 use roead::aamp::ParameterIO;
 
-use crate::Endian;
+use super::cpp_classes::ActorLink::*;
+use crate::Platform;
+
+// Size of the ParamIO and Resource bases, which are not modelled; together
+// with `ActorLink` they make up the full 0x4a8/0x778 class
+const BASE_SIZE_WIIU: u32 = 0x1d0;
+const BASE_SIZE_NX: u32 = 0x2b0;
 
-const CLASS_SIZE_WIIU: usize = 0x4a8;
-const CLASS_SIZE_NX: usize = 0x778;
+// These hold for every stock link in a dump, which `audit_dump` in
+// `calc::audit` checks. The two stock links available here need only 0x44 on
+// Wii U and 0x40 on Switch, but two files can't show the rest is unused, so
+// the margin is kept.
+const OVERHEAD_WIIU: u32 = 0x64;
+const OVERHEAD_NX: u32 = 0x44;
+// The tag buffer holds one CRC32 per tag and is allocated from the resource
+// heap, which rounds it up to its alignment
+const TAG_SIZE: u32 = size_of::<u32>() as u32;
+const HEAP_ALIGNMENT_WIIU: u32 = 4;
+const HEAP_ALIGNMENT_NX: u32 = 8;
 
-const OVERHEAD_WIIU: usize = 0x64;
-const OVERHEAD_NX: usize = 0x44;
-const TAG_SIZE: usize = std::mem::size_of::<u32>();
+pub fn parse_size(bytes: &[u8], platform: Platform) -> Option<u32> {
+    parse_pio_size(&ParameterIO::from_binary(bytes).ok()?, platform)
+}
 
-pub fn parse_size(bytes: &[u8], endian: Endian) -> Option<u32> {
-    let mut total_size: usize = match endian {
-        Endian::Big => super::PARSE_CONST_WIIU as usize + CLASS_SIZE_WIIU + OVERHEAD_WIIU,
-        Endian::Little => super::PARSE_CONST_NX as usize + CLASS_SIZE_NX + OVERHEAD_NX,
+pub fn parse_pio_size(a: &ParameterIO, platform: Platform) -> Option<u32> {
+    let (total_size, alignment) = match platform {
+        Platform::WiiU => {
+            (
+                super::PARSE_CONST_WIIU
+                    + BASE_SIZE_WIIU
+                    + size_of::<ActorLink<u32>>() as u32
+                    + OVERHEAD_WIIU,
+                HEAP_ALIGNMENT_WIIU,
+            )
+        }
+        Platform::Switch => {
+            (
+                super::PARSE_CONST_NX
+                    + BASE_SIZE_NX
+                    + size_of::<ActorLink<u64>>() as u32
+                    + OVERHEAD_NX,
+                HEAP_ALIGNMENT_NX,
+            )
+        }
     };
-    let a = ParameterIO::from_binary(bytes).ok()?;
 
-    if let Some(tags) = a.param_root.objects.get("Tags") {
-        total_size += TAG_SIZE * tags.len();
-    }
+    // The LinkTarget user strings, the tag names and any Affixes or UserName
+    // objects are all SafeStrings left pointing into the loaded file, so
+    // their storage is the file itself: both stock files fit with no further
+    // cost, despite their strings differing in length by a third. The
+    // overheads' margin is left to cover them if that doesn't hold for others.
+    // Only the tag hashes are copied out, into the class's tag buffer.
+    let tags = a
+        .param_root
+        .objects
+        .get("Tags")
+        .map_or(0, |tags| tags.len() as u32);
+    let tag_buffer = tags
+        .saturating_mul(TAG_SIZE)
+        .checked_next_multiple_of(alignment)?;
 
-    Some(total_size as u32)
+    total_size.checked_add(tag_buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use super::ActorLink;
+
+    #[test]
+    fn class_size() {
+        assert_eq!(
+            super::BASE_SIZE_WIIU as usize + size_of::<ActorLink<u32>>(),
+            0x4a8
+        );
+        assert_eq!(
+            super::BASE_SIZE_NX as usize + size_of::<ActorLink<u64>>(),
+            0x778
+        );
+    }
 }
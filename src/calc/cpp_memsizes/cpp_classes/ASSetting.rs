@@ -0,0 +1,18 @@
+use super::{agl::*, Bool32, SafeString, SeadBuffer, F32};
+
+#[repr(C)]
+pub struct BlenderBone<T> {
+    key_obj: ParameterObj<T>,
+    key_name: Parameter<T, SafeString<T>>,
+    bones: SeadBuffer<T>, // sead::Buffer<BoneParam>
+    bone_list: ParameterList<T>,
+    list: ParameterList<T>,
+}
+
+#[repr(C)]
+pub struct BoneParam<T> {
+    obj: ParameterObj<T>,
+    bone_name: Parameter<T, SafeString<T>>,
+    blend_rate: Parameter<T, F32>,
+    recursible: Parameter<T, Bool32>,
+}
@@ -0,0 +1,43 @@
+use super::{agl::*, Float, SafeString, SeadBuffer};
+
+// The user strings are SafeStrings pointing into the resource data, so no
+// storage is allocated for them no matter how long they are
+#[repr(C)]
+pub struct Users<T> {
+    obj: ParameterObj<T>,
+    profile: Parameter<T, SafeString<T>>,
+    actor_capture: Parameter<T, SafeString<T>>,
+    as_: Parameter<T, SafeString<T>>,
+    model: Parameter<T, SafeString<T>>,
+    anim: Parameter<T, SafeString<T>>,
+    ai_program: Parameter<T, SafeString<T>>,
+    gparam: Parameter<T, SafeString<T>>,
+    damage_param: Parameter<T, SafeString<T>>,
+    rg_config_list: Parameter<T, SafeString<T>>,
+    rg_blend_weight: Parameter<T, SafeString<T>>,
+    awareness: Parameter<T, SafeString<T>>,
+    physics: Parameter<T, SafeString<T>>,
+    chemical: Parameter<T, SafeString<T>>,
+    attention: Parameter<T, SafeString<T>>,
+    elink: Parameter<T, SafeString<T>>,
+    slink: Parameter<T, SafeString<T>>,
+    xlink: Parameter<T, SafeString<T>>,
+    drop_table: Parameter<T, SafeString<T>>,
+    shop_data: Parameter<T, SafeString<T>>,
+    recipe: Parameter<T, SafeString<T>>,
+    lod: Parameter<T, SafeString<T>>,
+    bone_control: Parameter<T, SafeString<T>>,
+    ai_schedule: Parameter<T, SafeString<T>>,
+    life_condition: Parameter<T, SafeString<T>>,
+    umii: Parameter<T, SafeString<T>>,
+}
+
+#[repr(C)]
+pub struct ActorLink<T> {
+    users: Users<T>, // LinkTarget
+    actor_name_jpn: Parameter<T, SafeString<T>>,
+    priority: Parameter<T, SafeString<T>>,
+    actor_scale: Parameter<T, Float>,
+    tags_obj: ParameterObj<T>,
+    tags: SeadBuffer<T>, // sead::Buffer<u32>, one CRC32 per tag
+}
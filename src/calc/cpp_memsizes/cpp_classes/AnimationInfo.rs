@@ -0,0 +1,15 @@
+use super::{agl::*, SeadBuffer};
+
+#[repr(C)]
+pub struct AnimInfo<T> {
+    list: ParameterList<T>,
+    name: T,                   // const char*
+    name_hash: u32,            // u32
+    param_objs: SeadBuffer<T>, // sead::Buffer<AnimParamObj>
+}
+
+#[repr(C)]
+pub struct AnimParamObj<T> {
+    obj:    ParameterObj<T>,
+    params: SeadBuffer<T>, // sead::Buffer<agl::utl::ParameterBase*>
+}
@@ -0,0 +1,16 @@
+use super::{agl::*, SafeString, SeadBuffer, S32};
+
+#[repr(C)]
+pub struct BoneGroup<T> {
+    param_obj: ParameterObj<T>,
+    group_name: Parameter<T, SafeString<T>>,
+    bones_obj: ParameterObj<T>,
+    bones: SeadBuffer<T>, // sead::Buffer<Bone>
+    list: ParameterList<T>,
+}
+
+#[repr(C)]
+pub struct Bone<T> {
+    name: Parameter<T, SafeString<T>>,
+    bone_index: S32,
+}
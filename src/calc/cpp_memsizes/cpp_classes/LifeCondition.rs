@@ -0,0 +1,25 @@
+use super::{agl::*, Float, SafeString, SeadBuffer};
+
+#[repr(C)]
+pub struct ConditionList<T> {
+    obj:   ParameterObj<T>,
+    items: SeadBuffer<T>, // sead::Buffer<ConditionItem>
+}
+
+#[repr(C)]
+pub struct ConditionItem<T> {
+    name:  Parameter<T, SafeString<T>>,
+    value: u32, // enum
+}
+
+#[repr(C)]
+pub struct DisplayDistance<T> {
+    obj:  ParameterObj<T>,
+    item: Parameter<T, Float>,
+}
+
+#[repr(C)]
+pub struct YLimit<T> {
+    obj:  ParameterObj<T>,
+    item: Parameter<T, SafeString<T>>,
+}
@@ -223,3 +223,23 @@ pub struct ShapeParamObj<T> {
     floor_code: Parameter<T, FixedSafeString32<T>>,
     item_code_disable_stick: Parameter<T, Bool32>,
 }
+
+#[repr(C)]
+pub struct SupportBoneMainBone<T> {
+    base: ParameterList<T>,
+    obj: ParameterObj<T>,
+    bone_name: Parameter<T, SafeString<T>>,
+    base_bone_name: Parameter<T, SafeString<T>>,
+    support_bones: SeadBuffer<T>,
+}
+
+#[repr(C)]
+pub struct SupportBoneData<T> {
+    base: ParameterObj<T>,
+    bone_name: Parameter<T, SafeString<T>>,
+    bend_type: Parameter<T, SafeString<T>>,
+    translate: Parameter<T, Vector3f>,
+    rotate: Parameter<T, Vector3f>,
+    weight: Parameter<T, Float>,
+    bone_index: u32,
+}
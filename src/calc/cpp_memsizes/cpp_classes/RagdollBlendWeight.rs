@@ -0,0 +1,17 @@
+use super::{agl::*, SafeString, SeadBuffer, F32, S32};
+
+#[repr(C)]
+pub struct State<T> {
+    list: ParameterList<T>,
+    setting_obj: ParameterObj<T>,
+    name: Parameter<T, SafeString<T>>,
+    num_input: Parameter<T, S32>,
+    input_weights: SeadBuffer<T>, // sead::Buffer<InputWeight>
+}
+
+#[repr(C)]
+pub struct InputWeight<T> {
+    obj: ParameterObj<T>,
+    rigid_name: Parameter<T, SafeString<T>>,
+    blend_rate: Parameter<T, F32>,
+}
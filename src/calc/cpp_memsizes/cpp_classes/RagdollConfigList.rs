@@ -0,0 +1,23 @@
+use super::{agl::*, SafeString, SeadBuffer, F32};
+
+#[repr(C)]
+pub struct ImpulseParam<T> {
+    obj: ParameterObj<T>,
+    entry_name: Parameter<T, SafeString<T>>,
+    file_name: Parameter<T, SafeString<T>>,
+    config: T, // RagdollConfig*
+}
+
+#[repr(C)]
+pub struct BodyParam<T> {
+    obj: ParameterObj<T>,
+    rigid_body_name: Parameter<T, SafeString<T>>,
+    friction_scale: Parameter<T, F32>,
+    buoyancy_scale: Parameter<T, F32>,
+}
+
+#[repr(C)]
+pub struct ParamList<T> {
+    list:  ParameterList<T>,
+    items: SeadBuffer<T>,
+}
@@ -3,10 +3,17 @@
 
 pub mod AIProgram;
 pub mod ASList;
+pub mod ASSetting;
+pub mod ActorLink;
+pub mod AnimationInfo;
+pub mod BoneControl;
 pub mod DropTable;
 pub mod GParamList;
+pub mod LifeCondition;
 pub mod ModelList;
 pub mod Physics;
+pub mod RagdollBlendWeight;
+pub mod RagdollConfigList;
 pub mod Recipe;
 pub mod ShopData;
 pub mod agl;
@@ -44,6 +51,28 @@ pub struct Vector4f {
     z: f32,
     w: f32,
 }
+pub struct Color4f {
+    r: f32,
+    g: f32,
+    b: f32,
+    a: f32,
+}
+pub struct Quatf {
+    x: f32,
+    y: f32,
+    z: f32,
+    w: f32,
+}
+
+// The data of one curve in an `agl::utl::ParameterCurve`, which also keeps a
+// `sead::hostio::Curve` to evaluate it
+#[repr(C)]
+pub struct Curve<T> {
+    mCurve:     T,         // sead::hostio::ICurve*
+    mNumUse:    u32,       // u32
+    mCurveType: u32,       // u32
+    mFloats:    [f32; 30], // f32[30]
+}
 
 #[repr(C)]
 pub struct SafeString<T> {
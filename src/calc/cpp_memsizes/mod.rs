@@ -1,16 +1,47 @@
 pub mod baiprog;
+pub mod baniminfo;
 pub mod baslist;
+pub mod bassetting;
+pub mod bbonectrl;
 pub mod bdrop;
 pub mod bgparamlist;
+pub mod blifecondition;
 pub mod bmodellist;
 pub mod bphysics;
+pub mod bphyssb;
 pub mod brecipe;
+pub mod brgbw;
+pub mod brgconfiglist;
 pub mod bshop;
 pub mod bxml;
 pub mod cpp_classes;
 
-const PARSE_CONST_WIIU: u32 = 0xe4;
-const PARSE_CONST_NX: u32 = 0x168;
+use std::collections::HashSet;
+
+use roead::{
+    aamp::{Parameter, ParameterIO, ParameterList},
+    types::Curve,
+};
+
+use crate::Platform;
+
+const PARSE_CONST_WIIU: u32 = Platform::WiiU.parse_const();
+const PARSE_CONST_NX: u32 = Platform::Switch.parse_const();
+
+// The `*_OVERHEAD_*` constants of baniminfo, bbonectrl, blifecondition,
+// bphyssb, brgbw and brgconfiglist cover what their loaders allocate beyond
+// the class and the entries modelled for each type. Nothing documents those
+// allocations, so each is the most the stock table allows: the smallest stock
+// entry of the type less an empty 0x40 byte document, the parse constant, the
+// class size and, for bphyssb, its copy of the setup param. This is checked by
+// `estimate_empty_pio` in `calc`. The entry costs are only checked against
+// real files by `audit_dump` in `calc::audit`, given a game dump.
+
+/// Returns the size of `len` entries of `size` bytes each, or `None` if it
+/// doesn't fit in a `u32`.
+fn array_size(len: usize, size: u32) -> Option<u32> {
+    u32::try_from(len).ok()?.checked_mul(size)
+}
 
 // Leaving this here in case I need it for some reason in the future
 #[allow(dead_code)]
@@ -53,3 +84,124 @@ const fn cpp_align(list: &[u32], alignment: &u32) -> u32 {
     }
     size * a
 }
+
+/// Returns the size of the binary that a parameter IO serializes to, as
+/// recorded in its header, without serializing it. Like the writer, this
+/// stores identical parameter data and strings only once, with each entry
+/// aligned to 4 bytes.
+pub(crate) fn binary_size(pio: &ParameterIO) -> usize {
+    fn align(size: usize) -> usize {
+        (size + 3) & !3
+    }
+
+    /// The data written for a parameter, and whether the writer hashes it as
+    /// floats, which keeps it from being shared with integer data.
+    fn data(param: &Parameter) -> (Vec<u8>, bool) {
+        fn floats(values: &[f32]) -> Vec<u8> {
+            values.iter().flat_map(|v| v.to_le_bytes()).collect()
+        }
+        fn curves(curves: &[Curve]) -> Vec<u8> {
+            curves
+                .iter()
+                .flat_map(|c| {
+                    [c.a.to_le_bytes(), c.b.to_le_bytes()]
+                        .concat()
+                        .into_iter()
+                        .chain(floats(&c.floats))
+                })
+                .collect()
+        }
+        fn buffer(len: usize, data: Vec<u8>) -> Vec<u8> {
+            (len as u32).to_le_bytes().into_iter().chain(data).collect()
+        }
+        match param {
+            Parameter::Bool(v) => ((*v as u32).to_le_bytes().to_vec(), false),
+            Parameter::F32(v) => (v.to_le_bytes().to_vec(), true),
+            Parameter::I32(v) => (v.to_le_bytes().to_vec(), false),
+            Parameter::U32(v) => (v.to_le_bytes().to_vec(), false),
+            Parameter::Vec2(v) => (floats(&[v.x, v.y]), false),
+            Parameter::Vec3(v) => (floats(&[v.x, v.y, v.z]), false),
+            Parameter::Vec4(v) => (floats(&[v.x, v.y, v.z, v.t]), false),
+            Parameter::Color(v) => (floats(&[v.r, v.g, v.b, v.a]), false),
+            Parameter::Quat(v) => (floats(&[v.a, v.b, v.c, v.d]), false),
+            Parameter::Curve1(v) => (curves(v.as_ref()), false),
+            Parameter::Curve2(v) => (curves(v.as_ref()), false),
+            Parameter::Curve3(v) => (curves(v.as_ref()), false),
+            Parameter::Curve4(v) => (curves(v.as_ref()), false),
+            Parameter::BufferInt(v) => {
+                let data = v.iter().flat_map(|i| i.to_le_bytes()).collect();
+                (buffer(v.len(), data), false)
+            }
+            Parameter::BufferU32(v) => {
+                let data = v.iter().flat_map(|i| i.to_le_bytes()).collect();
+                (buffer(v.len(), data), false)
+            }
+            Parameter::BufferF32(v) => (buffer(v.len(), floats(v)), true),
+            Parameter::BufferBinary(v) => (buffer(v.len(), v.clone()), false),
+            _ => (vec![], false),
+        }
+    }
+
+    #[derive(Default)]
+    struct Sizes<'a> {
+        structures: usize,
+        data: HashSet<(Vec<u8>, bool)>,
+        data_size: usize,
+        strings: HashSet<&'a str>,
+        string_size: usize,
+    }
+
+    fn walk<'a>(list: &'a ParameterList, sizes: &mut Sizes<'a>) {
+        sizes.structures += 0xc;
+        for (_, object) in list.objects.iter() {
+            sizes.structures += 0x8;
+            for (_, param) in object.iter() {
+                sizes.structures += 0x8;
+                if let Ok(string) = param.as_str() {
+                    if sizes.strings.insert(string) {
+                        sizes.string_size += align(string.len() + 1);
+                    }
+                } else {
+                    let data = data(param);
+                    let size = align(data.0.len());
+                    if sizes.data.insert(data) {
+                        sizes.data_size += size;
+                    }
+                }
+            }
+        }
+        for (_, child) in list.lists.iter() {
+            walk(child, sizes);
+        }
+    }
+
+    let mut sizes = Sizes::default();
+    walk(&pio.param_root, &mut sizes);
+    0x30 + align(pio.data_type.len() + 1) + sizes.structures + sizes.data_size + sizes.string_size
+}
+
+#[cfg(test)]
+mod tests {
+    use roead::aamp::ParameterIO;
+
+    #[test]
+    fn binary_size() {
+        for file in [
+            "test/Armor.baiprog",
+            "test/Armor_001_Upper.bmodellist",
+            "test/Armor_002_Upper.brecipe",
+            "test/Assassin_Senior.bdrop",
+            "test/Assassin_Senior.bxml",
+            "test/Enemy_Bokoblin_Gold.bdmgparam",
+            "test/NpcGerudoQueenBattle.baiprog",
+            "test/Npc_TripMaster_08.bshop",
+            "test/Player_Link.bgparamlist",
+            "test/Player_Link.bphysics",
+        ] {
+            let pio = ParameterIO::from_binary(std::fs::read(file).unwrap()).unwrap();
+            // The writer leaves off the padding after the last string
+            let written = (pio.to_binary().len() + 3) & !3;
+            assert_eq!(super::binary_size(&pio), written, "{file}");
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! A minimal yaz0 decoder, so calculations can look inside compressed files
+//! without the optional `yaz0` and `complex` features.
+
+use std::borrow::Cow;
+
+/// Returns the decompressed contents of yaz0 data, or the data itself if it
+/// is not compressed. Returns `None` if the compressed data is truncated.
+pub(crate) fn yaz0(bytes: &[u8]) -> Option<Cow<'_, [u8]>> {
+    yaz0_prefix(bytes, usize::MAX)
+}
+
+/// Like [`yaz0()`], but stops decompressing once `limit` bytes are
+/// available, for when only a file's header is needed.
+pub(crate) fn yaz0_prefix(bytes: &[u8], limit: usize) -> Option<Cow<'_, [u8]>> {
+    if bytes.get(0..4)? != b"Yaz0" {
+        return Some(Cow::Borrowed(bytes));
+    }
+    let size = (u32::from_be_bytes(bytes.get(4..8)?.try_into().ok()?) as usize).min(limit);
+    // The header's size can't be trusted, so only reserve as much as the input
+    let mut out: Vec<u8> = Vec::with_capacity(size.min(bytes.len()));
+    let mut pos = 16;
+    while out.len() < size {
+        let code = *bytes.get(pos)?;
+        pos += 1;
+        for bit in 0..8 {
+            if out.len() >= size {
+                break;
+            }
+            if code & (0x80 >> bit) != 0 {
+                out.push(*bytes.get(pos)?);
+                pos += 1;
+            } else {
+                let (b1, b2) = (*bytes.get(pos)? as usize, *bytes.get(pos + 1)? as usize);
+                pos += 2;
+                let distance = (((b1 & 0xf) << 8) | b2) + 1;
+                let count = match b1 >> 4 {
+                    0 => {
+                        pos += 1;
+                        *bytes.get(pos - 1)? as usize + 0x12
+                    }
+                    n => n + 2,
+                };
+                let start = out.len().checked_sub(distance)?;
+                // A copy can run past the size in the header, which is all
+                // the data there is
+                for i in 0..count.min(size - out.len()) {
+                    out.push(out[start + i]);
+                }
+            }
+        }
+    }
+    Some(Cow::Owned(out))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn stop_at_header_size() {
+        // One literal, then a copy of 0x11 bytes into a 4 byte file
+        let mut bytes = b"Yaz0\0\0\0\x04".to_vec();
+        bytes.resize(16, 0);
+        bytes.extend([0x80, b'a', 0xf0, 0x00]);
+        assert_eq!(super::yaz0(&bytes).unwrap().as_ref(), b"aaaa");
+        assert_eq!(super::yaz0_prefix(&bytes, 2).unwrap().as_ref(), b"aa");
+    }
+
+    #[cfg(feature = "yaz0")]
+    #[test]
+    fn match_yaz0_crate() {
+        let bytes = std::fs::read("test/AirOcta_Tag.sbactorpack").unwrap();
+        let expected = yaz0::Yaz0Archive::new(std::io::Cursor::new(&bytes))
+            .unwrap()
+            .decompress()
+            .unwrap();
+        assert_eq!(super::yaz0(&bytes).unwrap().as_ref(), expected.as_slice());
+    }
+}
@@ -0,0 +1,136 @@
+//! Infers the platform a resource was built for from its own bytes, so files
+//! from mixed Wii U and Switch folders can be calculated without the caller
+//! having to know where each one came from.
+//!
+//! The formats the game reads in their native byte order (SARC, BYML, BARS
+//! and BFRES) give the platform away in their byte-order marks, and BFRES
+//! headers also differ in layout. Havok packfiles record both the pointer size
+//! and byte order they were written for. AAMP files are little endian with the
+//! same layout on both platforms, so they cannot be told apart.
+
+use super::decompress;
+use crate::{Endian, Platform};
+
+/// The longest header read while detecting, after decompression.
+const HEADER_SIZE: usize = 0x20;
+
+const HAVOK_PACKFILE_MAGIC: [u8; 8] = [0x57, 0xe0, 0xe0, 0x57, 0x10, 0xc0, 0xc0, 0x10];
+
+/// How much a detected platform can be trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Confidence {
+    /// The file's markers disagree with each other, e.g. a Switch-layout
+    /// BFRES with a big endian byte-order mark, which the game would not load
+    /// on either platform. The platform is taken from the marker that
+    /// matters most for calculations (the layout or pointer size).
+    Low,
+    /// Every marker in the file points to the same platform.
+    High,
+}
+
+/// A platform inferred from a resource's bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Detection {
+    pub platform:   Platform,
+    pub confidence: Confidence,
+}
+
+impl Detection {
+    fn new(platform: Platform, confidence: Confidence) -> Self {
+        Self {
+            platform,
+            confidence,
+        }
+    }
+
+    fn from_bom(bom: Endian) -> Self {
+        Self::new(bom.into(), Confidence::High)
+    }
+
+    /// Detects from a format whose layout and byte order should both match
+    /// the platform, trusting the layout if they do not.
+    fn from_layout(layout: Platform, bom: Option<Endian>) -> Self {
+        let confidence = if bom == Some(layout.endian()) {
+            Confidence::High
+        } else {
+            Confidence::Low
+        };
+        Self::new(layout, confidence)
+    }
+}
+
+/// Infers the platform of a resource from its bytes, which may be Yaz0
+/// compressed. Returns `None` if the format is not recognized or is the same
+/// on both platforms (as AAMP is).
+pub fn detect_platform(bytes: &[u8]) -> Option<Detection> {
+    let bytes = decompress::yaz0_prefix(bytes, HEADER_SIZE)?;
+    let bytes = bytes.as_ref();
+    match bytes.get(0..4)? {
+        b"SARC" => bom(bytes, 0x6).map(Detection::from_bom),
+        b"BARS" => bom(bytes, 0x8).map(Detection::from_bom),
+        [b'B', b'Y', 0, 1..=4] => Some(Detection::from_bom(Endian::Big)),
+        [b'Y', b'B', 1..=4, 0] => Some(Detection::from_bom(Endian::Little)),
+        b"FRES" if bytes.get(4..8)? == b"    " => {
+            Some(Detection::from_layout(Platform::Switch, bom(bytes, 0xc)))
+        }
+        b"FRES" => Some(Detection::from_layout(Platform::WiiU, bom(bytes, 0x8))),
+        _ if bytes.get(0..8)? == HAVOK_PACKFILE_MAGIC => detect_havok(bytes),
+        _ => None,
+    }
+}
+
+/// Reads the layout rules of a Havok packfile, which start with the pointer
+/// size and whether the file is little endian.
+fn detect_havok(bytes: &[u8]) -> Option<Detection> {
+    let layout = match bytes.get(0x10)? {
+        4 => Platform::WiiU,
+        8 => Platform::Switch,
+        _ => return None,
+    };
+    let bom = match bytes.get(0x11)? {
+        0 => Some(Endian::Big),
+        1 => Some(Endian::Little),
+        _ => None,
+    };
+    Some(Detection::from_layout(layout, bom))
+}
+
+fn bom(bytes: &[u8], offset: usize) -> Option<Endian> {
+    match bytes.get(offset..offset + 2)? {
+        [0xfe, 0xff] => Some(Endian::Big),
+        [0xff, 0xfe] => Some(Endian::Little),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_test_files() {
+        for (file, platform) in [
+            ("test/A-1_Dynamic.smubin", Some(Platform::WiiU)),
+            ("test/AirOcta_Tag.sbactorpack", Some(Platform::Switch)),
+            ("test/savedataformat.ssarc", Some(Platform::WiiU)),
+            ("test/Animal_Bass.Tex1.sbfres", Some(Platform::WiiU)),
+            ("test/Obj_TreeWhiteBirch_A_01.hkrb", Some(Platform::WiiU)),
+            ("test/Armor.baiprog", None),
+        ] {
+            let bytes = std::fs::read(file).unwrap();
+            let detection = detect_platform(&bytes);
+            assert_eq!(detection.map(|d| d.platform), platform, "{file}");
+            assert!(detection.map_or(true, |d| d.confidence == Confidence::High));
+        }
+    }
+
+    #[test]
+    fn detect_mismatched_markers() {
+        let mut bfres = b"FRES    \0\x05\0\0\xfe\xff".to_vec();
+        bfres.resize(HEADER_SIZE, 0);
+        assert_eq!(
+            detect_platform(&bfres),
+            Some(Detection::new(Platform::Switch, Confidence::Low))
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! A minimal reader for Havok packfiles and tagfiles, used to estimate RSTB
+//! values for the Complex Havok types.
+//!
+//! Cloth, navmesh, tera mesh and ragdoll resources are only sized from their
+//! file size, and their values don't scale with the objects they hold. There
+//! are no stock files of these types to measure what their sections, faces or
+//! rigid bodies cost, so their objects are read only to reject data that
+//! isn't Havok. Cloth, tera mesh and ragdoll values use the `extra` and
+//! `size_factor` of their factory info, so a
+//! [`FactoryTable`](super::FactoryTable) can override them.
+
+use crate::{Endian, Platform};
+
+const PACKFILE_MAGIC: [u8; 8] = [0x57, 0xe0, 0xe0, 0x57, 0x10, 0xc0, 0xc0, 0x10];
+const PACKFILE_HEADER_SIZE: usize = 0x40;
+
+// The flat overheads measured for stock navmeshes (0x19c on Wii U, 0x290 on
+// Switch) less the parse constant and the class size. Packfiles are loaded in
+// place, and 1382 of the 1426 stock Wii U navmeshes fit a rounded size plus
+// the flat overhead, so the faces and edges aren't given a cost of their own.
+const NAVMESH_INSTANCE_WIIU: u32 = 0x90;
+const NAVMESH_INSTANCE_NX: u32 = 0xe0;
+
+/// Calculates the allocation made for a Havok resource beyond the file
+/// itself, the parse constant and the factory size, returning `None` if the
+/// data cannot be read as a Havok file or the type has no model.
+pub(crate) fn parse_size(bytes: &[u8], ext: &str, platform: Platform) -> Option<u32> {
+    count_objects(bytes)?;
+    if ext == "hknm2" {
+        return Some(match platform {
+            Platform::WiiU => NAVMESH_INSTANCE_WIIU,
+            Platform::Switch => NAVMESH_INSTANCE_NX,
+        });
+    }
+    if !matches!(ext, "brgcon" | "hkcl" | "hktmrb") {
+        return None;
+    }
+    let info = super::get_factory_info(ext, platform);
+    let rounded = super::round_32(bytes.len());
+    Some(((rounded as f32 * info.size_factor) as u32).saturating_add(info.extra))
+}
+
+/// Counts the serialized objects in a Havok packfile or tagfile, returning
+/// `None` if the data is not Havok.
+pub(crate) fn count_objects(bytes: &[u8]) -> Option<u32> {
+    if bytes.get(0..8)? == PACKFILE_MAGIC {
+        read_packfile(bytes)
+    } else if matches!(bytes.get(4..8)?, b"TAG0" | b"TCM0") {
+        read_tagfile(bytes)
+    } else {
+        None
+    }
+}
+
+struct Reader<'a> {
+    bytes:  &'a [u8],
+    endian: Endian,
+}
+
+impl Reader<'_> {
+    fn u32(&self, offset: usize) -> Option<u32> {
+        let raw: [u8; 4] = self.bytes.get(offset..offset + 4)?.try_into().ok()?;
+        Some(match self.endian {
+            Endian::Big => u32::from_be_bytes(raw),
+            Endian::Little => u32::from_le_bytes(raw),
+        })
+    }
+
+    fn c_str(&self, offset: usize) -> Option<&str> {
+        let slice = self.bytes.get(offset..)?;
+        let len = slice.iter().position(|c| *c == 0)?;
+        std::str::from_utf8(&slice[..len]).ok()
+    }
+}
+
+struct Section<'a> {
+    tag: &'a [u8],
+    start: usize,
+    local_fixups: usize,
+    virtual_fixups: usize,
+    exports: usize,
+}
+
+fn read_packfile(bytes: &[u8]) -> Option<u32> {
+    let endian = if *bytes.get(0x11)? == 1 {
+        Endian::Little
+    } else {
+        Endian::Big
+    };
+    let reader = Reader { bytes, endian };
+    let version = reader.u32(0xc)?;
+    let num_sections = reader.u32(0x14)? as usize;
+    let (section_start, section_size) = if version >= 11 {
+        let padding = match endian {
+            Endian::Big => u16::from_be_bytes(bytes.get(0x3e..0x40)?.try_into().ok()?),
+            Endian::Little => u16::from_le_bytes(bytes.get(0x3e..0x40)?.try_into().ok()?),
+        };
+        (PACKFILE_HEADER_SIZE + padding as usize, 0x40)
+    } else {
+        (PACKFILE_HEADER_SIZE, 0x30)
+    };
+
+    let sections = (0..num_sections)
+        .map(|i| {
+            let offset = section_start + i * section_size;
+            let tag = bytes.get(offset..offset + 19)?;
+            let tag = &tag[..tag.iter().position(|c| *c == 0).unwrap_or(tag.len())];
+            let start = reader.u32(offset + 0x14)? as usize;
+            Some(Section {
+                tag,
+                start,
+                local_fixups: start + reader.u32(offset + 0x18)? as usize,
+                virtual_fixups: start + reader.u32(offset + 0x20)? as usize,
+                exports: start + reader.u32(offset + 0x24)? as usize,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let classnames = sections.iter().find(|s| s.tag == b"__classnames__")?;
+    let data = sections.iter().find(|s| s.tag == b"__data__")?;
+
+    // Every object has a virtual fixup naming its class
+    let mut objects = 0;
+    for offset in (data.virtual_fixups..data.exports).step_by(12) {
+        if reader.u32(offset)? == u32::MAX {
+            break;
+        }
+        let name_offset = classnames.start + reader.u32(offset + 8)? as usize;
+        if name_offset >= classnames.local_fixups {
+            return None;
+        }
+        reader.c_str(name_offset)?;
+        objects += 1;
+    }
+    Some(objects)
+}
+
+fn read_tagfile(bytes: &[u8]) -> Option<u32> {
+    let items = find_chunk(bytes, 0, bytes.len(), &[b"INDX", b"ITEM"])?;
+    // The first item is always a null placeholder
+    Some(((items.1 - items.0) / 12).saturating_sub(1) as u32)
+}
+
+/// Walks nested tagfile chunks, returning the data range of the chunk at
+/// `path` within the root chunk that starts at `offset`.
+fn find_chunk(
+    bytes: &[u8],
+    offset: usize,
+    end: usize,
+    path: &[&[u8; 4]],
+) -> Option<(usize, usize)> {
+    let root_size = chunk_size(bytes, offset)?;
+    let mut pos = offset + 8;
+    let root_end = usize::min(offset + root_size, end);
+    let (target, rest) = path.split_first()?;
+    while pos + 8 <= root_end {
+        let size = chunk_size(bytes, pos)?;
+        if size < 8 {
+            return None;
+        }
+        if bytes.get(pos + 4..pos + 8)? == *target {
+            return if rest.is_empty() {
+                Some((pos + 8, pos + size))
+            } else {
+                find_chunk(bytes, pos, pos + size, rest)
+            };
+        }
+        pos += size;
+    }
+    None
+}
+
+fn chunk_size(bytes: &[u8], offset: usize) -> Option<usize> {
+    let raw: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+    Some((u32::from_be_bytes(raw) & 0x3fff_ffff) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{calc::estimate_from_slice_and_name, Platform};
+
+    #[test]
+    fn read_packfile() {
+        let bytes = std::fs::read("test/Obj_TreeWhiteBirch_A_01.hkrb").unwrap();
+        assert_eq!(super::count_objects(&bytes), Some(16));
+        assert_eq!(super::count_objects(&bytes[..0x100]), None);
+    }
+
+    #[test]
+    fn estimate_havok() {
+        // None of these types is sized from its contents, so a stock rigid
+        // body packfile (3232 bytes) stands in for them
+        let bytes = std::fs::read("test/Obj_TreeWhiteBirch_A_01.hkrb").unwrap();
+        for (name, wiiu, nx) in [
+            // 3232 + parse constant + class size + flat navmesh overhead
+            ("NavMesh/MainField/9-8/0.hknm2", 3232 + 0x19c, 3232 + 0x290),
+            // 3232 * 2 + 0x2800 + parse constant + class size
+            (
+                "Physics/Cloth/Npc_Zora/Npc_Zora.hkcl",
+                3232 * 2 + 0x2800 + 0xe4 + 0xb8,
+                3232 * 2 + 0x2800 + 0x168 + 0xe8,
+            ),
+            // 3232 + 0x800 + parse constant + class size
+            (
+                "Physics/TeraMeshRigidBody/MainField/9-8.hktmrb",
+                3232 + 0x800 + 0xe4 + 0x28,
+                3232 + 0x800 + 0x168 + 0x48,
+            ),
+        ] {
+            assert_eq!(
+                estimate_from_slice_and_name(&bytes, name, Platform::WiiU),
+                Some(wiiu),
+                "{name}"
+            );
+            assert_eq!(
+                estimate_from_slice_and_name(&bytes, name, Platform::Switch),
+                Some(nx),
+                "{name}"
+            );
+        }
+        assert_eq!(
+            estimate_from_slice_and_name([0u8; 0x40], "Physics/Cloth/Bad.hkcl", Platform::WiiU),
+            None
+        );
+    }
+
+    #[test]
+    fn read_tagfile() {
+        // A TAG0 root holding an INDX chunk with two items, the first of which
+        // is the null placeholder
+        let mut bytes = vec![];
+        for (size, tag) in [(0x30u32, b"TAG0"), (0x28, b"INDX"), (0x20, b"ITEM")] {
+            bytes.extend_from_slice(&size.to_be_bytes());
+            bytes.extend_from_slice(tag);
+        }
+        bytes.resize(0x30, 0);
+        assert_eq!(super::count_objects(&bytes), Some(1));
+        assert_eq!(
+            estimate_from_slice_and_name(&bytes, "Physics/Cloth/Tag.hkcl", Platform::Switch),
+            Some(0x40 * 2 + 0x2800 + 0x168 + 0xe8)
+        );
+    }
+}
@@ -1,341 +1,605 @@
-use crate::Endian;
+//! Factory sizes and parse sizes for each resource type. The built-in values
+//! can be overridden at runtime with a [`FactoryTable`], which can also be
+//! loaded from JSON with the `json` feature.
 
+use std::{collections::BTreeMap, sync::RwLock};
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+use crate::Platform;
+
+/// How the parse size of a resource type is determined.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum ParseSize {
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum ParseSize {
+    /// A fixed parse size.
     Simple(u32),
+    /// The parse size depends on the file contents.
     Complex,
 }
 
-pub(crate) fn get_factory_info<S: AsRef<str>>(ext: S, endian: Endian) -> (u32, ParseSize) {
-    match ext.as_ref() {
+/// A quadratic in the rounded file size `r`, `((a * r + b) * r + c) * scale`,
+/// which replaces the usual sum for types whose value is not linear in the
+/// file size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Formula {
+    pub a:     f32,
+    pub b:     f32,
+    pub c:     f32,
+    pub scale: f32,
+}
+
+impl Formula {
+    pub(crate) fn apply(&self, rounded: u32) -> u32 {
+        let rounded = rounded as f32;
+        ((((self.a * rounded) + self.b) * rounded + self.c) * self.scale) as u32
+    }
+}
+
+/// The factory information for a resource type on one platform.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct FactoryInfo {
+    /// Size of the resource class created by the factory.
+    pub size: u32,
+    /// Parse size of the resource.
+    pub parse_size: ParseSize,
+    /// A flat amount added to every value of this type.
+    #[cfg_attr(feature = "json", serde(default))]
+    pub extra: u32,
+    /// Replaces the calculated value when the file's contents are available.
+    #[cfg_attr(feature = "json", serde(default))]
+    pub formula: Option<Formula>,
+    /// Fraction of the rounded file size added to values estimated from the
+    /// file size rather than its contents.
+    #[cfg_attr(feature = "json", serde(default))]
+    pub size_factor: f32,
+}
+
+impl From<(u32, ParseSize)> for FactoryInfo {
+    fn from((size, parse_size): (u32, ParseSize)) -> Self {
+        Self {
+            size,
+            parse_size,
+            extra: 0,
+            formula: None,
+            size_factor: 0.0,
+        }
+    }
+}
+
+/// The factory information for a resource type on both platforms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct FactoryEntry {
+    pub wiiu:   FactoryInfo,
+    pub switch: FactoryInfo,
+}
+
+/// A set of factory definitions keyed by resource type (e.g. `bfres`,
+/// `Tex1.bfres`). Types missing from the table use the built-in values, so a
+/// table only needs to contain the types it changes.
+///
+/// Install a table with [`set_factory_table()`](super::set_factory_table) to
+/// have it used by all calculations.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", serde(transparent))]
+pub struct FactoryTable {
+    entries: BTreeMap<String, FactoryEntry>,
+}
+
+/// Every resource type with built-in factory information.
+pub(crate) const BUILTIN_TYPES: &[&str] = &[
+    "sarc",
+    "pack",
+    "bactorpack",
+    "bmodelsh",
+    "beventpack",
+    "stera",
+    "stats",
+    "Tex.bfres",
+    "Tex1.bfres",
+    "Tex2.bfres",
+    "bfres",
+    "bcamanim",
+    "batpl",
+    "bnfprl",
+    "bplacement",
+    "hks",
+    "lua",
+    "bactcapt",
+    "bitemico",
+    "jpg",
+    "bmaptex",
+    "bstftex",
+    "bmapopen",
+    "breviewtex",
+    "bgdata",
+    "bgsvdata",
+    "hknm2",
+    "bmscdef",
+    "bars",
+    "bxml",
+    "bgparamlist",
+    "bmodellist",
+    "baslist",
+    "baiprog",
+    "bphysics",
+    "bchemical",
+    "bas",
+    "batcllist",
+    "batcl",
+    "baischedule",
+    "bdmgparam",
+    "brgconfiglist",
+    "brgconfig",
+    "brgbw",
+    "bawareness",
+    "bdrop",
+    "bshop",
+    "brecipe",
+    "blod",
+    "bbonectrl",
+    "blifecondition",
+    "bumii",
+    "baniminfo",
+    "byaml",
+    "bassetting",
+    "hkrb",
+    "hkrg",
+    "bphyssb",
+    "hkcl",
+    "hksc",
+    "hktmrb",
+    "brgcon",
+    "esetlist",
+    "bdemo",
+    "bfevfl",
+    "bfevtm",
+];
+
+pub(crate) static FACTORY_TABLE: RwLock<Option<FactoryTable>> = RwLock::new(None);
+
+impl FactoryTable {
+    /// Creates an empty table, in which every type uses the built-in values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a table containing the built-in values for every known type,
+    /// e.g. to dump as a starting point for a definition file.
+    pub fn builtin() -> Self {
+        Self {
+            entries: BUILTIN_TYPES
+                .iter()
+                .map(|ext| {
+                    (ext.to_string(), FactoryEntry {
+                        wiiu:   builtin_factory_info(ext, Platform::WiiU),
+                        switch: builtin_factory_info(ext, Platform::Switch),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Adds or replaces the definition for a resource type.
+    pub fn insert<S: Into<String>>(&mut self, ext: S, entry: FactoryEntry) {
+        self.entries.insert(ext.into(), entry);
+    }
+
+    /// Removes the definition for a resource type, returning it to the
+    /// built-in values.
+    pub fn remove(&mut self, ext: &str) -> Option<FactoryEntry> {
+        self.entries.remove(ext)
+    }
+
+    /// Gets the factory information for a resource type, falling back to the
+    /// built-in values if the table does not define it.
+    pub fn get(&self, ext: &str, platform: impl Into<Platform>) -> FactoryInfo {
+        let platform = platform.into();
+        match self.entries.get(ext) {
+            Some(entry) => {
+                match platform {
+                    Platform::WiiU => entry.wiiu,
+                    Platform::Switch => entry.switch,
+                }
+            }
+            None => builtin_factory_info(ext, platform),
+        }
+    }
+
+    /// *Requires the `json` feature.*
+    /// Parses a JSON factory definition file.
+    #[cfg(feature = "json")]
+    pub fn from_text<S: AsRef<str>>(text: S) -> crate::Result<Self> {
+        serde_json::from_str(text.as_ref()).map_err(|e| e.into())
+    }
+
+    /// *Requires the `json` feature.*
+    /// Generates a pretty-printed JSON factory definition file.
+    #[cfg(feature = "json")]
+    pub fn to_text(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Factory table should serialize without error")
+    }
+}
+
+pub(crate) fn get_factory_info(ext: &str, platform: Platform) -> FactoryInfo {
+    match FACTORY_TABLE
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+    {
+        Some(table) => table.get(ext, platform),
+        None => builtin_factory_info(ext, platform),
+    }
+}
+
+fn builtin_factory_info(ext: &str, platform: Platform) -> FactoryInfo {
+    let mut info = FactoryInfo::from(builtin_sizes(ext, platform));
+    match (ext, platform) {
+        ("beventpack", Platform::WiiU) => info.extra = 0xe0,
+        ("bfevfl", Platform::WiiU) => info.extra = 0x58,
+        ("hkrb", Platform::WiiU) => info.extra = 40,
+        ("bdmgparam", _) => {
+            info.formula = Some(Formula {
+                a:     -0.0018,
+                b:     6.6273,
+                c:     500.0,
+                scale: match platform {
+                    Platform::WiiU => 1.0,
+                    Platform::Switch => 1.5,
+                },
+            });
+            info.size_factor = 0.666;
+        }
+        // Cloth, tera mesh and ragdoll files are only checked to be Havok data
+        // and sized from their file size. No stock file of these types has been
+        // measured, so these amounts are unchecked guesses that leave room for
+        // the loaded objects: cloth setups get a second copy of the file.
+        ("hkcl", _) => {
+            info.extra = 0x2800;
+            info.size_factor = 1.0;
+        }
+        ("hktmrb", _) => info.extra = 0x800,
+        ("brgcon", _) => info.extra = 0x4000,
+        _ => (),
+    }
+    info
+}
+
+fn builtin_sizes(ext: &str, platform: Platform) -> (u32, ParseSize) {
+    match ext {
         "sarc" | "pack" | "bactorpack" | "bmodelsh" | "beventpack" | "stera" | "stats" => {
-            match endian {
-                Endian::Little => (0x68, ParseSize::Simple(0)),
-                Endian::Big => (0x3c, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x68, ParseSize::Simple(0)),
+                Platform::WiiU => (0x3c, ParseSize::Simple(0)),
             }
         }
         "Tex.bfres" | "Tex1.bfres" | "Tex2.bfres" => {
-            match endian {
-                Endian::Little => (0x38, ParseSize::Simple(0)),
-                Endian::Big => (0x20, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x38, ParseSize::Simple(0)),
+                Platform::WiiU => (0x20, ParseSize::Simple(0)),
             }
         }
         "bfres" => {
-            match endian {
-                Endian::Little => (0x1a8, ParseSize::Complex),
-                Endian::Big => (0x13c, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x1a8, ParseSize::Complex),
+                Platform::WiiU => (0x13c, ParseSize::Complex),
             }
         }
         "bcamanim" => {
-            match endian {
-                Endian::Little => (0x50, ParseSize::Complex),
-                Endian::Big => (0x2c, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x50, ParseSize::Complex),
+                Platform::WiiU => (0x2c, ParseSize::Complex),
             }
         }
         "batpl" | "bnfprl" => {
-            match endian {
-                Endian::Little => (0x40, ParseSize::Simple(0)),
-                Endian::Big => (0x24, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x40, ParseSize::Simple(0)),
+                Platform::WiiU => (0x24, ParseSize::Simple(0)),
             }
         }
         "bplacement" => {
-            match endian {
-                Endian::Little => (0x48, ParseSize::Simple(0)),
-                Endian::Big => (0x14, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x48, ParseSize::Simple(0)),
+                Platform::WiiU => (0x14, ParseSize::Simple(0)),
             }
         }
         "hks" | "lua" => {
-            match endian {
-                Endian::Little => (0x38, ParseSize::Simple(0)),
-                Endian::Big => (0x14, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x38, ParseSize::Simple(0)),
+                Platform::WiiU => (0x14, ParseSize::Simple(0)),
             }
         }
         "bactcapt" => {
-            match endian {
-                Endian::Little => (0x538, ParseSize::Simple(0)),
-                Endian::Big => (0x3b4, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x538, ParseSize::Simple(0)),
+                Platform::WiiU => (0x3b4, ParseSize::Simple(0)),
             }
         }
         "bitemico" => {
-            match endian {
-                Endian::Little => (0x60, ParseSize::Simple(0)),
-                Endian::Big => (0xd0, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x60, ParseSize::Simple(0)),
+                Platform::WiiU => (0xd0, ParseSize::Simple(0)),
             }
         }
         "jpg" => {
-            match endian {
-                Endian::Little => (0x80, ParseSize::Simple(0)),
-                Endian::Big => (0x174, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x80, ParseSize::Simple(0)),
+                Platform::WiiU => (0x174, ParseSize::Simple(0)),
             }
         }
         "bmaptex" => {
-            match endian {
-                Endian::Little => (0x60, ParseSize::Simple(0)),
-                Endian::Big => (0xd0, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x60, ParseSize::Simple(0)),
+                Platform::WiiU => (0xd0, ParseSize::Simple(0)),
             }
         }
         "bstftex" | "bmapopen" | "breviewtex" => {
-            match endian {
-                Endian::Little => (0x60, ParseSize::Simple(0)),
-                Endian::Big => (0xd0, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x60, ParseSize::Simple(0)),
+                Platform::WiiU => (0xd0, ParseSize::Simple(0)),
             }
         }
         "bgdata" => {
-            match endian {
-                Endian::Little => (0x140, ParseSize::Simple(0)),
-                Endian::Big => (0xcc, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x140, ParseSize::Simple(0)),
+                Platform::WiiU => (0xcc, ParseSize::Simple(0)),
             }
         }
         "bgsvdata" => {
-            match endian {
-                Endian::Little => (0x38, ParseSize::Simple(0)),
-                Endian::Big => (0x14, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x38, ParseSize::Simple(0)),
+                Platform::WiiU => (0x14, ParseSize::Simple(0)),
             }
         }
         "hknm2" => {
-            match endian {
-                Endian::Little => (0x48, ParseSize::Complex),
-                Endian::Big => (0x28, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x48, ParseSize::Complex),
+                Platform::WiiU => (0x28, ParseSize::Complex),
             }
         }
         "bmscdef" => {
-            match endian {
-                Endian::Little => (0x2a8, ParseSize::Complex),
-                Endian::Big => (0x1fc, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x2a8, ParseSize::Complex),
+                Platform::WiiU => (0x1fc, ParseSize::Complex),
             }
         }
         "bars" => {
-            match endian {
-                Endian::Little => (0xb0, ParseSize::Complex),
-                Endian::Big => (0x84, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0xb0, ParseSize::Complex),
+                Platform::WiiU => (0x84, ParseSize::Complex),
             }
         }
         "bxml" => {
-            match endian {
-                Endian::Little => (0x778, ParseSize::Complex),
-                Endian::Big => (0x4a8, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x778, ParseSize::Complex),
+                Platform::WiiU => (0x4a8, ParseSize::Complex),
             }
         }
         "bgparamlist" => {
-            match endian {
-                Endian::Little => (0x2c0, ParseSize::Complex),
-                Endian::Big => (0x248, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x2c0, ParseSize::Complex),
+                Platform::WiiU => (0x248, ParseSize::Complex),
             }
         }
         "bmodellist" => {
-            match endian {
-                Endian::Little => (0x7d0, ParseSize::Complex),
-                Endian::Big => (0x508, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x7d0, ParseSize::Complex),
+                Platform::WiiU => (0x508, ParseSize::Complex),
             }
         }
         "baslist" => {
-            match endian {
-                Endian::Little => (0x410, ParseSize::Complex),
-                Endian::Big => (0x2f4, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x410, ParseSize::Complex),
+                Platform::WiiU => (0x2f4, ParseSize::Complex),
             }
         }
         "baiprog" => {
-            match endian {
-                Endian::Little => (0x448, ParseSize::Complex),
-                Endian::Big => (0x30c, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x448, ParseSize::Complex),
+                Platform::WiiU => (0x30c, ParseSize::Complex),
             }
         }
         "bphysics" => {
-            match endian {
-                Endian::Little => (0x470, ParseSize::Complex),
-                Endian::Big => (0x324, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x470, ParseSize::Complex),
+                Platform::WiiU => (0x324, ParseSize::Complex),
             }
         }
         "bchemical" => {
-            match endian {
-                Endian::Little => (0x3c0, ParseSize::Complex),
-                Endian::Big => (0x2cc, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x3c0, ParseSize::Complex),
+                Platform::WiiU => (0x2cc, ParseSize::Complex),
             }
         }
         "bas" => {
-            match endian {
-                Endian::Little => (0x3c8, ParseSize::Complex),
-                Endian::Big => (0x2d0, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x3c8, ParseSize::Complex),
+                Platform::WiiU => (0x2d0, ParseSize::Complex),
             }
         }
         "batcllist" => {
-            match endian {
-                Endian::Little => (0x3f0, ParseSize::Complex),
-                Endian::Big => (0x2e4, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x3f0, ParseSize::Complex),
+                Platform::WiiU => (0x2e4, ParseSize::Complex),
             }
         }
         "batcl" => {
-            match endian {
-                Endian::Little => (0x428, ParseSize::Complex),
-                Endian::Big => (0x344, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x428, ParseSize::Complex),
+                Platform::WiiU => (0x344, ParseSize::Complex),
             }
         }
         "baischedule" => {
-            match endian {
-                Endian::Little => (0x2b8, ParseSize::Simple(0)),
-                Endian::Big => (0x244, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x2b8, ParseSize::Simple(0)),
+                Platform::WiiU => (0x244, ParseSize::Simple(0)),
             }
         }
         "bdmgparam" => {
-            match endian {
-                Endian::Little => (0x11d0, ParseSize::Simple(0x790)),
-                Endian::Big => (0x9f0, ParseSize::Simple(0x3c0)),
+            match platform {
+                Platform::Switch => (0x11d0, ParseSize::Simple(0x790)),
+                Platform::WiiU => (0x9f0, ParseSize::Simple(0x3c0)),
             }
         }
         "brgconfiglist" => {
-            match endian {
-                Endian::Little => (0x3d0, ParseSize::Complex),
-                Endian::Big => (0x2d4, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x3d0, ParseSize::Complex),
+                Platform::WiiU => (0x2d4, ParseSize::Complex),
             }
         }
         "brgconfig" => {
-            match endian {
-                Endian::Little => (0x42d8, ParseSize::Simple(0)),
-                Endian::Big => (0x2acc, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x42d8, ParseSize::Simple(0)),
+                Platform::WiiU => (0x2acc, ParseSize::Simple(0)),
             }
         }
         "brgbw" => {
-            match endian {
-                Endian::Little => (0x2c0, ParseSize::Complex),
-                Endian::Big => (0x248, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x2c0, ParseSize::Complex),
+                Platform::WiiU => (0x248, ParseSize::Complex),
             }
         }
         "bawareness" => {
-            match endian {
-                Endian::Little => (0xb38, ParseSize::Simple(0)),
-                Endian::Big => (0x70c, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0xb38, ParseSize::Simple(0)),
+                Platform::WiiU => (0x70c, ParseSize::Simple(0)),
             }
         }
         "bdrop" => {
-            match endian {
-                Endian::Little => (0x320, ParseSize::Complex),
-                Endian::Big => (0x27c, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x320, ParseSize::Complex),
+                Platform::WiiU => (0x27c, ParseSize::Complex),
             }
         }
         "bshop" => {
-            match endian {
-                Endian::Little => (0x320, ParseSize::Complex),
-                Endian::Big => (0x27c, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x320, ParseSize::Complex),
+                Platform::WiiU => (0x27c, ParseSize::Complex),
             }
         }
         "brecipe" => {
-            match endian {
-                Endian::Little => (0x320, ParseSize::Complex),
-                Endian::Big => (0x27c, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x320, ParseSize::Complex),
+                Platform::WiiU => (0x27c, ParseSize::Complex),
             }
         }
         "blod" => {
-            match endian {
-                Endian::Little => (0x3c0, ParseSize::Simple(0)),
-                Endian::Big => (0x2cc, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x3c0, ParseSize::Simple(0)),
+                Platform::WiiU => (0x2cc, ParseSize::Simple(0)),
             }
         }
         "bbonectrl" => {
-            match endian {
-                Endian::Little => (0x8d0, ParseSize::Complex),
-                Endian::Big => (0x564, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x8d0, ParseSize::Complex),
+                Platform::WiiU => (0x564, ParseSize::Complex),
             }
         }
         "blifecondition" => {
-            match endian {
-                Endian::Little => (0x4b0, ParseSize::Complex),
-                Endian::Big => (0x35c, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x4b0, ParseSize::Complex),
+                Platform::WiiU => (0x35c, ParseSize::Complex),
             }
         }
         "bumii" => {
-            match endian {
-                Endian::Little => (0x2b8, ParseSize::Simple(0)),
-                Endian::Big => (0x244, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x2b8, ParseSize::Simple(0)),
+                Platform::WiiU => (0x244, ParseSize::Simple(0)),
             }
         }
         "baniminfo" => {
-            match endian {
-                Endian::Little => (0x2c8, ParseSize::Complex),
-                Endian::Big => (0x24c, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x2c8, ParseSize::Complex),
+                Platform::WiiU => (0x24c, ParseSize::Complex),
             }
         }
         "byaml" => {
-            match endian {
-                Endian::Little => (0x20, ParseSize::Simple(0)),
-                Endian::Big => (0x14, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x20, ParseSize::Simple(0)),
+                Platform::WiiU => (0x14, ParseSize::Simple(0)),
             }
         }
         "bassetting" => {
-            match endian {
-                Endian::Little => (0x260, ParseSize::Complex),
-                Endian::Big => (0x1d8, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x260, ParseSize::Complex),
+                Platform::WiiU => (0x1d8, ParseSize::Complex),
             }
         }
         "hkrb" => {
-            match endian {
-                Endian::Little => (0x20, ParseSize::Simple(0)),
-                Endian::Big => (0x14, ParseSize::Simple(40)),
+            match platform {
+                Platform::Switch => (0x20, ParseSize::Simple(0)),
+                Platform::WiiU => (0x14, ParseSize::Simple(40)),
             }
         }
         "hkrg" => {
-            match endian {
-                Endian::Little => (0x20, ParseSize::Simple(0)),
-                Endian::Big => (0x14, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x20, ParseSize::Simple(0)),
+                Platform::WiiU => (0x14, ParseSize::Simple(0)),
             }
         }
         "bphyssb" => {
-            match endian {
-                Endian::Little => (0x5b0, ParseSize::Complex),
-                Endian::Big => (0x384, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x5b0, ParseSize::Complex),
+                Platform::WiiU => (0x384, ParseSize::Complex),
             }
         }
         "hkcl" => {
-            match endian {
-                Endian::Little => (0xe8, ParseSize::Complex),
-                Endian::Big => (0xb8, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0xe8, ParseSize::Complex),
+                Platform::WiiU => (0xb8, ParseSize::Complex),
             }
         }
         "hksc" => {
-            match endian {
-                Endian::Little => (0x140, ParseSize::Complex),
-                Endian::Big => (0xe8, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x140, ParseSize::Complex),
+                Platform::WiiU => (0xe8, ParseSize::Complex),
             }
         }
         "hktmrb" => {
-            match endian {
-                Endian::Little => (0x48, ParseSize::Complex),
-                Endian::Big => (0x28, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x48, ParseSize::Complex),
+                Platform::WiiU => (0x28, ParseSize::Complex),
             }
         }
         "brgcon" => {
-            match endian {
-                Endian::Little => (0x48, ParseSize::Complex),
-                Endian::Big => (0x28, ParseSize::Complex),
+            match platform {
+                Platform::Switch => (0x48, ParseSize::Complex),
+                Platform::WiiU => (0x28, ParseSize::Complex),
             }
         }
         "esetlist" => {
-            match endian {
-                Endian::Little => (0x38, ParseSize::Simple(0)),
-                Endian::Big => (0x20, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x38, ParseSize::Simple(0)),
+                Platform::WiiU => (0x20, ParseSize::Simple(0)),
             }
         }
         "bdemo" => {
-            match endian {
-                Endian::Little => (0xb20, ParseSize::Simple(0)),
-                Endian::Big => (0x6cc, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0xb20, ParseSize::Simple(0)),
+                Platform::WiiU => (0x6cc, ParseSize::Simple(0)),
             }
         }
         "bfevfl" => {
-            match endian {
-                Endian::Little => (0x40, ParseSize::Simple(0)),
-                Endian::Big => (0x24, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x40, ParseSize::Simple(0)),
+                Platform::WiiU => (0x24, ParseSize::Simple(0)),
             }
         }
         "bfevtm" => {
-            match endian {
-                Endian::Little => (0x40, ParseSize::Simple(0)),
-                Endian::Big => (0x24, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x40, ParseSize::Simple(0)),
+                Platform::WiiU => (0x24, ParseSize::Simple(0)),
             }
         }
         _ => {
-            match endian {
-                Endian::Little => (0x38, ParseSize::Simple(0)),
-                Endian::Big => (0x20, ParseSize::Simple(0)),
+            match platform {
+                Platform::Switch => (0x38, ParseSize::Simple(0)),
+                Platform::WiiU => (0x20, ParseSize::Simple(0)),
             }
         }
     }
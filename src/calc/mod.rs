@@ -34,48 +34,122 @@
 //!
 //! A full list of supported estimation types:
 //! - `baiprog`
+//! - `baniminfo`
 //! - `bas`
 //! - `bphysics`
+//! - `bphyssb`
 //! - `baslist`
+//! - `bassetting`
+//! - `bbonectrl`
 //! - `bdrop`
 //! - `bgparamlist`
+//! - `blifecondition`
 //! - `brecipe`
+//! - `brgbw`
+//! - `brgconfiglist`
 //! - `bshop`
 //! - `bxml`
 //! - `bfres`
+//! - `brgcon`
+//! - `hkcl`
+//! - `hknm2`
+//! - `hksc`
+//! - `hktmrb`
 //!
 //! **To repeat:** the `calc` functions can only do infallible calculations,
 //! and otherwise return `None`. The `estimate` functions will first try the
 //! infallible calculation, then try and estimate, and return `None` only if
-//! neither works.
+//! neither works. The `_with_options` variants take [`EstimateOptions`] to
+//! make estimates tighter or more conservative, pad them with a margin, or
+//! keep them from falling below the stock value. With the `yaml` feature,
+//! [`estimate_from_aamp_text()`] estimates parameter archives straight from
+//! their YAML source.
 //!
-//! *A final note*: As mentioned elsewhere, the `endian` parameter should be
-//! used as a shorthand for Wii U/Switch more generally. Pass `Endian::Big` for
-//! Wii U files, even if they are actually in little endian, and pass
-//! `Endian:Little` for Switch files, even if they are actually in big endian.
+//! *A final note*: Calculations depend on the platform, not just on byte
+//! order, so they take a [`Platform`]. An
+//! [`Endian`](crate::Endian) is still accepted as shorthand for the platform
+//! whose RSTB uses it (`Big` for Wii U, `Little` for Switch), regardless of
+//! the byte order of the file being calculated. When the platform is not
+//! known, [`detect_platform()`] can usually infer it from the file itself,
+//! and [`estimate_auto()`] does so before estimating.
+//!
+//! All of the functions here dispatch through the default
+//! [`CalculatorRegistry`], so support for new types, or replacements for the
+//! built-in calculations, can be added with [`register_calculator()`].
 
+pub mod audit;
+mod bfres;
+pub mod calibrate;
 #[cfg(feature = "complex")]
 mod cpp_memsizes;
+mod decompress;
+mod detect;
+mod havok;
 mod info;
+mod options;
+mod registry;
+mod sarc;
 
 use std::path::Path;
 
 #[cfg(feature = "complex")]
 use cpp_memsizes::{
-    baiprog, baslist, bdrop, bgparamlist, bmodellist, bphysics, brecipe, bshop, bxml,
+    baiprog, baniminfo, baslist, bassetting, bbonectrl, bdrop, bgparamlist, blifecondition,
+    bmodellist, bphysics, bphyssb, brecipe, brgbw, brgconfiglist, bshop, bxml,
+};
+pub use detect::{detect_platform, Confidence, Detection};
+use info::get_factory_info;
+pub use info::{FactoryEntry, FactoryInfo, FactoryTable, Formula, ParseSize};
+pub use options::{EstimateMode, EstimateOptions};
+pub use registry::{
+    register_calculator, set_default_registry, with_default_registry, BuiltinCalculator,
+    Calculator, CalculatorRegistry, ResourceType,
 };
-use info::{get_factory_info, ParseSize};
 
-use crate::{Endian, Result};
+use crate::{Platform, Result};
 
 #[inline]
 fn round_32(size: usize) -> u32 {
     ((size as isize + 31) & -32) as u32
 }
 
+/// Gets the size of a file's data, which is the decompressed size from the
+/// header for Yaz0 compressed files.
+pub(crate) fn data_size(bytes: &[u8]) -> usize {
+    match bytes.get(0..8) {
+        Some(&[b'Y', b'a', b'z', b'0', a, b, c, d]) => u32::from_be_bytes([a, b, c, d]) as usize,
+        _ => bytes.len(),
+    }
+}
+
+/// Replaces the factory information used by all calculations with the given
+/// table, or restores the built-in values if `None` is passed. Types missing
+/// from the table still use the built-in values.
+pub fn set_factory_table(table: Option<FactoryTable>) {
+    *info::FACTORY_TABLE
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = table;
+}
+
+/// Gets the resource type used for calculations from a file name: everything
+/// after the first dot, without the Yaz0 `s` prefix.
+fn resource_ext(name: &str) -> Option<&str> {
+    let raw_ext = &name[name.find('.')? + 1..];
+    Some(match raw_ext {
+        "sarc" => "sarc",
+        "Tex.sbfres" => "Tex.bfres",
+        "Tex1.sbfres" => "Tex1.bfres",
+        "Tex2.sbfres" => "Tex2.bfres",
+        _ => raw_ext.strip_prefix('s').unwrap_or(raw_ext),
+    })
+}
+
 /// Infallibly calculate an RSTB value from a file on disk, returning `None` if
 /// the type is not supported.
-pub fn calc_from_file<P: AsRef<Path>>(file: P, endian: Endian) -> Result<Option<u32>> {
+pub fn calc_from_file<P: AsRef<Path>>(
+    file: P,
+    platform: impl Into<Platform>,
+) -> Result<Option<u32>> {
     Ok(calc_from_slice_and_name(
         std::fs::read(file.as_ref())?,
         file.as_ref()
@@ -83,7 +157,7 @@ pub fn calc_from_file<P: AsRef<Path>>(file: P, endian: Endian) -> Result<Option<
             .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Path not a file"))?
             .to_str()
             .unwrap_or_default(),
-        endian,
+        platform,
     ))
 }
 
@@ -92,101 +166,81 @@ pub fn calc_from_file<P: AsRef<Path>>(file: P, endian: Endian) -> Result<Option<
 pub fn calc_from_slice_and_name<B: AsRef<[u8]>>(
     bytes: B,
     name: &str,
-    endian: Endian,
+    platform: impl Into<Platform>,
 ) -> Option<u32> {
     let bytes = bytes.as_ref();
     if bytes.len() < 8 {
         None
     } else {
-        calc_or_estimate_from_bytes_and_name(bytes, name, endian, false)
+        with_default_registry(|registry| {
+            registry.calc_from_bytes_and_name(bytes, name, platform.into())
+        })
     }
 }
 
 /// Infallibly calculate an RSTB value from an uncompressed file size and
 /// filename, returning `None` if the type is not supported.
-pub fn calc_from_size_and_name(filesize: usize, name: &str, endian: Endian) -> Option<u32> {
-    calc_or_estimate_from_size_and_name(filesize, name, endian, false)
+pub fn calc_from_size_and_name(
+    filesize: usize,
+    name: &str,
+    platform: impl Into<Platform>,
+) -> Option<u32> {
+    with_default_registry(|registry| {
+        registry.calc_from_size_and_name(filesize, name, platform.into())
+    })
 }
 
-fn calc_or_estimate_from_size_and_name(
+pub(crate) fn builtin_from_size_and_name(
     filesize: usize,
     name: &str,
-    endian: Endian,
+    platform: Platform,
     estimate: bool,
 ) -> Option<u32> {
-    if let Some(dot_pos) = name.find('.') {
-        let rounded = round_32(filesize);
-        let raw_ext = &name[dot_pos + 1..];
-        let ext = match raw_ext {
-            "sarc" => "sarc",
-            _ => {
-                if let Some(ext) = raw_ext.strip_prefix('s') {
-                    ext
-                } else {
-                    raw_ext
-                }
-            }
-        };
-        let (size, parse_size) = get_factory_info(ext, endian);
-        match parse_size {
-            ParseSize::Simple(parse_size) => {
-                Some(match endian {
-                    Endian::Big => {
-                        rounded
-                            + 0xe4
-                            + size
-                            + parse_size
-                            + match ext {
-                                "beventpack" => 0xe0,
-                                "bfevfl" => 0x58,
-                                "hkrb" => 40,
-                                "bdmgparam" => (rounded as f32 * 0.666) as u32,
-                                _ => 0,
-                            }
-                    }
-                    Endian::Little => {
-                        rounded
-                            + 0x168
-                            + size
-                            + parse_size
-                            + match ext {
-                                "bdmgparam" => (rounded as f32 * 0.666) as u32,
-                                _ => 0,
-                            }
-                    }
-                })
-            }
-            ParseSize::Complex => {
-                if estimate {
-                    match ext {
-                        "baniminfo" => {
-                            Some(
-                                ((rounded as f32 * (if filesize > 36864 { 1.5 } else { 4.0 }))
-                                    as u32
-                                    + 0xe4
-                                    + 0x24c)
-                                    * match endian {
-                                        Endian::Big => 1,
-                                        Endian::Little => 2,
-                                    },
-                            )
-                        }
-                        "bfres" => Some(estimate_bfres(filesize, endian)),
-                        _ => estimate_aamp(filesize, name, endian),
-                    }
-                } else {
-                    None
+    builtin_from_size_and_ext(filesize, resource_ext(name)?, platform, estimate)
+}
+
+/// Calculates or estimates a value from an uncompressed file size for a
+/// resource type given by its extension (e.g. `"bxml"`), for callers that
+/// have no resource name.
+pub(crate) fn builtin_from_size_and_ext(
+    filesize: usize,
+    ext: &str,
+    platform: Platform,
+    estimate: bool,
+) -> Option<u32> {
+    let rounded = round_32(filesize);
+    let info = get_factory_info(ext, platform);
+    match info.parse_size {
+        ParseSize::Simple(parse_size) => {
+            Some(
+                rounded
+                    + platform.parse_const()
+                    + info.size
+                    + parse_size
+                    + info.extra
+                    + (rounded as f32 * info.size_factor) as u32,
+            )
+        }
+        ParseSize::Complex => {
+            if estimate {
+                match ext {
+                    "baniminfo" => Some(estimate_baniminfo(filesize, platform)),
+                    "bfres" => Some(estimate_bfres(filesize, platform)),
+                    _ => estimate_aamp(filesize, ext, platform),
                 }
+            } else {
+                None
             }
         }
-    } else {
-        None
     }
 }
 
 /// Infallibly calculate *or* estimate an RSTB value from a file on disk,
 /// returning `None` if the type is not supported.
-pub fn estimate_from_file<P: AsRef<Path>>(file: P, endian: Endian) -> Result<Option<u32>> {
+pub fn estimate_from_file<P: AsRef<Path>>(
+    file: P,
+    platform: impl Into<Platform>,
+) -> Result<Option<u32>> {
     Ok(estimate_from_bytes_and_name(
         &std::fs::read(file.as_ref())?,
         file.as_ref()
@@ -194,14 +248,20 @@ pub fn estimate_from_file<P: AsRef<Path>>(file: P, endian: Endian) -> Result<Opt
             .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Path not a file"))?
             .to_str()
             .unwrap_or_default(),
-        endian,
+        platform,
     ))
 }
 
 /// Infallibly calculate *or* estimate an RSTB value from an uncompressed file size and
 /// filename, returning `None` if the type is not supported.
-pub fn estimate_from_size_and_name(filesize: usize, name: &str, endian: Endian) -> Option<u32> {
-    calc_or_estimate_from_size_and_name(filesize, name, endian, true)
+pub fn estimate_from_size_and_name(
+    filesize: usize,
+    name: &str,
+    platform: impl Into<Platform>,
+) -> Option<u32> {
+    with_default_registry(|registry| {
+        registry.estimate_from_size_and_name(filesize, name, platform.into())
+    })
 }
 
 /// Infallibly calculate *or* estimate an RSTB value from a byte slice and
@@ -209,138 +269,273 @@ pub fn estimate_from_size_and_name(filesize: usize, name: &str, endian: Endian)
 pub fn estimate_from_slice_and_name<B: AsRef<[u8]>>(
     bytes: B,
     name: &str,
-    endian: Endian,
+    platform: impl Into<Platform>,
 ) -> Option<u32> {
     let bytes = bytes.as_ref();
     if bytes.len() < 8 {
         None
     } else {
-        estimate_from_bytes_and_name(bytes, name, endian)
+        estimate_from_bytes_and_name(bytes, name, platform)
     }
 }
 
 /// Infallibly calculate an RSTB value from an uncompressed file size and
 /// filename, returning `None` if the type is not supported.
-pub fn estimate_from_bytes_and_name(bytes: &[u8], name: &str, endian: Endian) -> Option<u32> {
-    calc_or_estimate_from_bytes_and_name(bytes, name, endian, true)
+pub fn estimate_from_bytes_and_name(
+    bytes: &[u8],
+    name: &str,
+    platform: impl Into<Platform>,
+) -> Option<u32> {
+    with_default_registry(|registry| {
+        registry.estimate_from_bytes_and_name(bytes, name, platform.into())
+    })
 }
 
-fn calc_or_estimate_from_bytes_and_name(
+/// Infallibly calculate *or* estimate an RSTB value from a byte slice and
+/// filename, applying the mode, margin and floor of the given options to
+/// estimates. Returns `None` if the type is not supported.
+pub fn estimate_from_slice_and_name_with_options<B: AsRef<[u8]>>(
+    bytes: B,
+    name: &str,
+    platform: impl Into<Platform>,
+    options: &EstimateOptions,
+) -> Option<u32> {
+    let bytes = bytes.as_ref();
+    if bytes.len() < 8 {
+        None
+    } else {
+        options.estimate_bytes(bytes, name, platform.into())
+    }
+}
+
+/// Infallibly calculate *or* estimate an RSTB value from an uncompressed file
+/// size and filename, applying the margin and floor of the given options to
+/// estimates. Returns `None` if the type is not supported.
+pub fn estimate_from_size_and_name_with_options(
+    filesize: usize,
+    name: &str,
+    platform: impl Into<Platform>,
+    options: &EstimateOptions,
+) -> Option<u32> {
+    options.estimate_size(filesize, name, platform.into())
+}
+
+/// *Requires the `complex` feature.*
+/// Calculate *or* estimate an RSTB value for a parsed parameter archive of
+/// the given type (e.g. `"bphysics"`) without serializing it, so editors can
+/// show a live value. Uses the built-in parsers, ignoring any calculators
+/// registered for the type. Returns `None` if the type is not supported.
+#[cfg(feature = "complex")]
+pub fn estimate_from_pio(
+    pio: &roead::aamp::ParameterIO,
+    resource_type: impl Into<ResourceType>,
+    platform: impl Into<Platform>,
+) -> Option<u32> {
+    let (resource_type, platform) = (resource_type.into(), platform.into());
+    let filesize = cpp_memsizes::binary_size(pio);
+    let rounded = round_32(filesize);
+    let parse_size = match resource_type.as_str() {
+        "baiprog" => baiprog::parse_pio_size(pio, platform),
+        "baniminfo" => baniminfo::parse_pio_size(pio, platform),
+        "baslist" => baslist::parse_pio_size(pio, platform),
+        "bassetting" => bassetting::parse_pio_size(pio, platform),
+        "bbonectrl" => bbonectrl::parse_pio_size(pio, platform),
+        "bdrop" => bdrop::parse_pio_size(pio, platform),
+        "bgparamlist" => bgparamlist::parse_pio_size(pio, platform),
+        "blifecondition" => blifecondition::parse_pio_size(pio, platform),
+        "bmodellist" => bmodellist::parse_pio_size(pio, platform),
+        "bphysics" => bphysics::parse_pio_size(pio, platform),
+        "bphyssb" => bphyssb::parse_pio_size(pio, platform),
+        "brecipe" => brecipe::parse_pio_size(pio, platform),
+        "brgbw" => brgbw::parse_pio_size(pio, platform),
+        "brgconfiglist" => brgconfiglist::parse_pio_size(pio, platform),
+        "bshop" => bshop::parse_pio_size(pio, platform),
+        "bxml" => bxml::parse_pio_size(pio, platform),
+        // Types without a parser are calculated as they are from their bytes
+        ext => {
+            return match get_factory_info(ext, platform).formula {
+                Some(formula) => Some(formula.apply(rounded)),
+                None => builtin_from_size_and_ext(filesize, ext, platform, true),
+            };
+        }
+    };
+    Some(rounded + parse_size?)
+}
+
+/// *Requires the `yaml` feature.*
+/// Calculate *or* estimate an RSTB value for a parameter archive in roead's
+/// YAML text format, as with [`estimate_from_pio()`], so source files can be
+/// checked without converting them to binary. The type is taken from the
+/// name, which may keep a `.yml` or `.yaml` suffix (e.g.
+/// `Actor/Physics/Enemy_Lizalfos.bphysics.yml`). Returns `None` if the text
+/// cannot be parsed or the type is not supported.
+#[cfg(feature = "yaml")]
+pub fn estimate_from_aamp_text(
+    name: &str,
+    text: &str,
+    platform: impl Into<Platform>,
+) -> Option<u32> {
+    let name = name
+        .strip_suffix(".yml")
+        .or_else(|| name.strip_suffix(".yaml"))
+        .unwrap_or(name);
+    let pio = roead::aamp::ParameterIO::from_text(text).ok()?;
+    estimate_from_pio(&pio, ResourceType::from_name(name)?, platform)
+}
+
+/// Infallibly calculate *or* estimate an RSTB value from a file on disk,
+/// detecting its platform from its contents. Returns `None` if the type is
+/// not supported or the platform cannot be detected.
+pub fn estimate_auto_from_file<P: AsRef<Path>>(file: P) -> Result<Option<(u32, Detection)>> {
+    Ok(estimate_auto(
+        std::fs::read(file.as_ref())?,
+        file.as_ref()
+            .file_name()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Path not a file"))?
+            .to_str()
+            .unwrap_or_default(),
+    ))
+}
+
+/// Infallibly calculate *or* estimate an RSTB value from a byte slice and
+/// filename, detecting the platform from the data with [`detect_platform()`].
+/// Returns `None` if the type is not supported or the platform cannot be
+/// detected, rather than guessing.
+pub fn estimate_auto<B: AsRef<[u8]>>(bytes: B, name: &str) -> Option<(u32, Detection)> {
+    let bytes = bytes.as_ref();
+    let detection = detect_platform(bytes)?;
+    estimate_from_slice_and_name(bytes, name, detection.platform).map(|value| (value, detection))
+}
+
+pub(crate) fn builtin_from_bytes_and_name(
     bytes: &[u8],
     name: &str,
-    endian: Endian,
+    platform: Platform,
     estimate: bool,
 ) -> Option<u32> {
-    if let Some(dot_pos) = name.find('.') {
-        let filesize = match &bytes[0..4] {
-            b"Yaz0" => u32::from_be_bytes(bytes[4..8].try_into().ok()?) as usize,
-            _ => bytes.len(),
-        };
+    if let Some(ext) = resource_ext(name) {
+        let filesize = data_size(bytes);
         let rounded = round_32(filesize);
-        let raw_ext = &name[dot_pos + 1..];
-        let ext = match raw_ext {
-            "sarc" => "sarc",
-            _ => {
-                if let Some(ext) = raw_ext.strip_prefix('s') {
-                    ext
-                } else {
-                    raw_ext
-                }
-            }
-        };
-        let (size, parse_size) = get_factory_info(ext, endian);
-        match parse_size {
+        let info = get_factory_info(ext, platform);
+        let size = info.size;
+        match info.parse_size {
             ParseSize::Simple(parse_size) => {
-                Some(match endian {
-                    Endian::Big => {
-                        rounded
-                            + 0xe4
-                            + size
-                            + parse_size
-                            + match ext {
-                                "beventpack" => 0xe0,
-                                "bfevfl" => 0x58,
-                                "hkrb" => 40,
-                                "bdmgparam" => {
-                                    let rounded = rounded as f32;
-                                    return Some(
-                                        ((((-0.0018 * rounded) + 6.6273) * rounded) + 500.0) as u32,
-                                    );
-                                }
-                                _ => 0,
-                            }
-                    }
-                    Endian::Little => {
-                        rounded
-                            + 0x168
-                            + size
-                            + parse_size
-                            + match ext {
-                                "bdmgparam" => {
-                                    let rounded = rounded as f32;
-                                    return Some(
-                                        (((((-0.0018 * rounded) + 6.6273) * rounded) + 500.0) * 1.5)
-                                            as u32,
-                                    );
+                if let Some(formula) = info.formula {
+                    return Some(formula.apply(rounded));
+                }
+                Some(
+                    rounded
+                        + platform.parse_const()
+                        + size
+                        + parse_size
+                        + info.extra
+                        + match ext {
+                            "Tex.bfres" | "Tex1.bfres" | "Tex2.bfres" => {
+                                // Archives that can't be read keep the plain
+                                // calculation, or get the size heuristic when
+                                // estimating
+                                match bfres::parse_texture_size(bytes, platform) {
+                                    Some(extra) => extra,
+                                    None if estimate => {
+                                        return Some(estimate_bfres(filesize, platform));
+                                    }
+                                    None => 0,
                                 }
-                                _ => 0,
                             }
-                    }
-                })
+                            _ => 0,
+                        },
+                )
             }
             ParseSize::Complex => {
                 if estimate {
                     match ext {
                         #[cfg(feature = "complex")]
-                        "baiprog" => Some(rounded + baiprog::parse_size(bytes, endian)?),
+                        "baiprog" => Some(rounded + baiprog::parse_size(bytes, platform)?),
+                        #[cfg(feature = "complex")]
                         "baniminfo" => {
                             Some(
-                                ((rounded as f32 * (if filesize > 36864 { 1.5 } else { 4.0 }))
-                                    as u32
-                                    + 0xe4
-                                    + 0x24c)
-                                    * match endian {
-                                        Endian::Big => 1,
-                                        Endian::Little => 2,
-                                    },
+                                baniminfo::parse_size(bytes, platform)
+                                    .map(|parse_size| rounded + parse_size)
+                                    .unwrap_or_else(|| estimate_baniminfo(filesize, platform)),
                             )
                         }
+                        #[cfg(not(feature = "complex"))]
+                        "baniminfo" => Some(estimate_baniminfo(filesize, platform)),
                         #[cfg(feature = "complex")]
-                        "baslist" => Some(rounded + baslist::parse_size(bytes, endian)?),
+                        "baslist" => Some(rounded + baslist::parse_size(bytes, platform)?),
                         #[cfg(feature = "complex")]
-                        "bdrop" => Some(rounded + bdrop::parse_size(bytes, endian)?),
-                        "bfres" => Some(estimate_bfres(filesize, endian)),
+                        "bassetting" => Some(rounded + bassetting::parse_size(bytes, platform)?),
                         #[cfg(feature = "complex")]
-                        "bgparamlist" => Some(rounded + bgparamlist::parse_size(bytes, endian)?),
+                        "bbonectrl" => Some(rounded + bbonectrl::parse_size(bytes, platform)?),
                         #[cfg(feature = "complex")]
-                        "bmodellist" => Some(rounded + bmodellist::parse_size(bytes, endian)?),
+                        "bdrop" => Some(rounded + bdrop::parse_size(bytes, platform)?),
+                        "bfres" => {
+                            // The model costs are only measured on two files, so they
+                            // may raise the size heuristic but never lower it
+                            let by_size = estimate_bfres(filesize, platform);
+                            Some(match bfres::parse_size(bytes, platform) {
+                                Some(extra) => {
+                                    by_size.max(
+                                        (rounded + size + platform.parse_const())
+                                            .saturating_add(extra),
+                                    )
+                                }
+                                None => by_size,
+                            })
+                        }
                         #[cfg(feature = "complex")]
-                        "bphysics" => Some(rounded + bphysics::parse_size(bytes, endian)?),
+                        "bgparamlist" => Some(rounded + bgparamlist::parse_size(bytes, platform)?),
                         #[cfg(feature = "complex")]
-                        "brecipe" => Some(rounded + brecipe::parse_size(bytes, endian)?),
+                        "blifecondition" => {
+                            Some(rounded + blifecondition::parse_size(bytes, platform)?)
+                        }
                         #[cfg(feature = "complex")]
-                        "bshop" => Some(rounded + bshop::parse_size(bytes, endian)?),
+                        "bmodellist" => Some(rounded + bmodellist::parse_size(bytes, platform)?),
                         #[cfg(feature = "complex")]
-                        "bxml" => Some(rounded + bxml::parse_size(bytes, endian)?),
-                        "hknm2" => {
-                            Some(
-                                rounded
-                                    + match endian {
-                                        Endian::Big => 0x19c,
-                                        Endian::Little => 0x290,
-                                    },
-                            )
+                        "bphysics" => Some(rounded + bphysics::parse_size(bytes, platform)?),
+                        #[cfg(feature = "complex")]
+                        "bphyssb" => Some(rounded + bphyssb::parse_size(bytes, platform)?),
+                        #[cfg(feature = "complex")]
+                        "brecipe" => Some(rounded + brecipe::parse_size(bytes, platform)?),
+                        #[cfg(feature = "complex")]
+                        "brgbw" => Some(rounded + brgbw::parse_size(bytes, platform)?),
+                        #[cfg(feature = "complex")]
+                        "brgconfiglist" => {
+                            Some(rounded + brgconfiglist::parse_size(bytes, platform)?)
+                        }
+                        #[cfg(feature = "complex")]
+                        "bshop" => Some(rounded + bshop::parse_size(bytes, platform)?),
+                        #[cfg(feature = "complex")]
+                        "bxml" => Some(rounded + bxml::parse_size(bytes, platform)?),
+                        "brgcon" | "hkcl" | "hknm2" | "hktmrb" => {
+                            match havok::parse_size(bytes, ext, platform) {
+                                Some(extra) => {
+                                    Some(
+                                        (rounded + size + platform.parse_const())
+                                            .saturating_add(extra),
+                                    )
+                                }
+                                // Compressed or otherwise unreadable navmeshes
+                                // fall back to a flat overhead
+                                None => {
+                                    match (ext, platform) {
+                                        ("hknm2", Platform::WiiU) => Some(rounded + 0x19c),
+                                        ("hknm2", Platform::Switch) => Some(rounded + 0x290),
+                                        _ => None,
+                                    }
+                                }
+                            }
                         }
                         "hksc" => {
                             Some(
                                 rounded
-                                    + match endian {
-                                        Endian::Big => 0x74cc,
-                                        Endian::Little => 0x9c00,
+                                    + match platform {
+                                        Platform::WiiU => 0x74cc,
+                                        Platform::Switch => 0x9c00,
                                     },
                             )
                         }
-                        _ => estimate_aamp(filesize, name, endian),
+                        _ => estimate_aamp(filesize, ext, platform),
                     }
                 } else {
                     None
@@ -352,17 +547,16 @@ fn calc_or_estimate_from_bytes_and_name(
     }
 }
 
-fn estimate_aamp(filesize: usize, name: &str, endian: Endian) -> Option<u32> {
+fn estimate_aamp(filesize: usize, ext: &str, platform: Platform) -> Option<u32> {
     let mut size = (filesize as f32) * 1.05;
-    let ext = &name[name.rfind('.')? + 1..];
     if ext == "bas" {
         size *= 1.05;
     };
     if ext == "bassetting" {
         size = (round_32(size as usize)
-            + match endian {
-                Endian::Big => 0xe4 + 0x1d8,
-                Endian::Little => 0x168 + 0x260,
+            + match platform {
+                Platform::WiiU => 0xe4 + 0x1d8,
+                Platform::Switch => 0x168 + 0x260,
             }) as f32
             + (size * 2.75)
     } else if ext == "bdmgparam" {
@@ -456,16 +650,24 @@ fn estimate_aamp(filesize: usize, name: &str, endian: Endian) -> Option<u32> {
             _ => return None,
         };
     }
-    Some(match endian {
-        Endian::Big => size,
-        Endian::Little => size * 1.5,
+    Some(match platform {
+        Platform::WiiU => size,
+        Platform::Switch => size * 1.5,
     } as u32)
 }
 
-fn estimate_bfres(filesize: usize, endian: Endian) -> u32 {
+fn estimate_baniminfo(filesize: usize, platform: Platform) -> u32 {
+    ((round_32(filesize) as f32 * (if filesize > 36864 { 1.5 } else { 4.0 })) as u32 + 0xe4 + 0x24c)
+        * match platform {
+            Platform::WiiU => 1,
+            Platform::Switch => 2,
+        }
+}
+
+fn estimate_bfres(filesize: usize, platform: Platform) -> u32 {
     (filesize as f32
-        * match endian {
-            Endian::Big => {
+        * match platform {
+            Platform::WiiU => {
                 match filesize {
                     (0..500) => 7.0,
                     (500..750) => 5.0,
@@ -479,7 +681,7 @@ fn estimate_bfres(filesize: usize, endian: Endian) -> u32 {
                     _ => 1.45,
                 }
             }
-            Endian::Little => {
+            Platform::Switch => {
                 match filesize {
                     (0..1_250) => 9.5,
                     (1_250..2_500) => 6.0,
@@ -501,7 +703,7 @@ mod tests {
 
     use all_asserts::assert_ge;
 
-    use crate::Endian;
+    use crate::{Endian, Platform};
 
     #[test]
     fn calc_sizes() {
@@ -530,6 +732,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn factory_table() {
+        let builtin = super::FactoryTable::builtin();
+        let mut table = super::FactoryTable::new();
+        assert_eq!(table.get("bfevfl", Endian::Big).extra, 0x58);
+        assert_eq!(
+            table.get("bfevfl", Endian::Big),
+            builtin.get("bfevfl", Endian::Big)
+        );
+        let mut entry = super::FactoryEntry {
+            wiiu:   table.get("bfevfl", Endian::Big),
+            switch: table.get("bfevfl", Endian::Little),
+        };
+        entry.wiiu.size = 0x30;
+        table.insert("bfevfl", entry);
+        assert_eq!(table.get("bfevfl", Endian::Big).size, 0x30);
+        assert_eq!(table.get("bfevtm", Endian::Big).size, 0x24);
+        #[cfg(feature = "json")]
+        assert_eq!(
+            super::FactoryTable::from_text(builtin.to_text()).unwrap(),
+            builtin
+        );
+    }
+
     #[test]
     fn estimate_sizes() {
         assert_ge!(
@@ -576,26 +802,329 @@ mod tests {
             super::estimate_from_size_and_name(27960, "Actor/ASSetting.bassetting", Endian::Little),
             Some(165864)
         );
+        let (value, detection) =
+            super::estimate_auto_from_file("test/FldObj_TreeRootTropical_A_Far.sbfres")
+                .unwrap()
+                .unwrap();
+        assert_eq!(detection.platform, Platform::WiiU);
+        assert_eq!(value, 191808); // Stock is 137816
+        assert_eq!(
+            super::estimate_auto_from_file("test/Armor.baiprog").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn estimate_options() {
+        use super::EstimateOptions;
+
+        let name = "Model/DgnMrgPrt_Dungeon061.bfres";
+        let balanced = super::estimate_from_size_and_name(42496, name, Platform::WiiU).unwrap();
+        let options = EstimateOptions {
+            margin: 0.1,
+            margin_bytes: 4,
+            ..Default::default()
+        };
+        assert_eq!(
+            super::estimate_from_size_and_name_with_options(42496, name, Platform::WiiU, &options),
+            Some((balanced as f64 * 1.1).ceil() as u32 + 4)
+        );
+        // Infallible calculations are never padded
+        assert_eq!(
+            super::estimate_from_size_and_name_with_options(
+                1000,
+                "Actor/Pack/Test.bactorpack",
+                Platform::WiiU,
+                &options
+            ),
+            super::calc_from_size_and_name(1000, "Actor/Pack/Test.bactorpack", Platform::WiiU)
+        );
+        #[cfg(feature = "botw-data")]
+        for options in [
+            EstimateOptions {
+                stock_floor: true,
+                ..Default::default()
+            },
+            EstimateOptions::conservative(),
+        ] {
+            assert_eq!(
+                super::estimate_from_size_and_name_with_options(
+                    42496,
+                    "Model/Animal_Bass.Tex1.sbfres",
+                    Platform::WiiU,
+                    &options
+                ),
+                Some(50688)
+            );
+        }
+        #[cfg(feature = "complex")]
+        {
+            use super::EstimateMode;
+
+            let bytes = read("test/Player_Link.bphysics").unwrap();
+            let name = "Actor/Physics/Player_Link.bphysics";
+            let estimate = |mode: EstimateMode| {
+                super::estimate_from_slice_and_name_with_options(
+                    &bytes,
+                    name,
+                    Platform::WiiU,
+                    &EstimateOptions {
+                        mode,
+                        ..Default::default()
+                    },
+                )
+                .unwrap()
+            };
+            let balanced = estimate(EstimateMode::Balanced);
+            assert_eq!(
+                Some(balanced),
+                super::estimate_from_slice_and_name(&bytes, name, Platform::WiiU)
+            );
+            assert_eq!(estimate(EstimateMode::Tight), balanced - 0x6c);
+            assert_ge!(estimate(EstimateMode::Conservative), balanced + 0xcc);
+        }
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn estimate_pio() {
+        use roead::aamp::ParameterIO;
+
+        for (file, name) in [
+            (
+                "test/Player_Link.bphysics",
+                "Actor/Physics/Player_Link.bphysics",
+            ),
+            (
+                "test/Assassin_Senior.bxml",
+                "Actor/ActorLink/Assassin_Senior.bxml",
+            ),
+            (
+                "test/Enemy_Bokoblin_Gold.bdmgparam",
+                "Actor/DamageParam/Enemy_Bokoblin_Gold.bdmgparam",
+            ),
+        ] {
+            let pio = ParameterIO::from_binary(read(file).unwrap()).unwrap();
+            let resource_type = super::ResourceType::from_name(name).unwrap();
+            for platform in [Platform::WiiU, Platform::Switch] {
+                assert_eq!(
+                    super::estimate_from_pio(&pio, resource_type.clone(), platform),
+                    super::estimate_from_slice_and_name(pio.to_binary(), name, platform),
+                    "{file}"
+                );
+            }
+        }
+    }
+
+    /// The types with overheads fitted to the stock table in `cpp_memsizes`
+    /// come out at exactly their smallest stock value for an empty document.
+    #[cfg(all(feature = "complex", feature = "botw-data"))]
+    #[test]
+    fn estimate_empty_pio() {
+        use roead::aamp::ParameterIO;
+
+        use crate::ResourceSizeTable;
+
+        let pio = ParameterIO::new();
+        assert_eq!(super::cpp_memsizes::binary_size(&pio), 0x40);
+        let stock = [
+            (
+                Platform::WiiU,
+                ResourceSizeTable::new_from_stock(Platform::WiiU),
+            ),
+            (
+                Platform::Switch,
+                ResourceSizeTable::new_from_stock(Platform::Switch),
+            ),
+        ];
+        // The smallest stock entry of each type on Wii U and on Switch
+        for smallest in [
+            [
+                "Actor/AnimationInfo/Dummy.baniminfo",
+                "Actor/AnimationInfo/Dummy.baniminfo",
+            ],
+            [
+                "Actor/BoneControl/Dummy.bbonectrl",
+                "Actor/BoneControl/Dummy.bbonectrl",
+            ],
+            [
+                "Actor/LifeCondition/Dummy.blifecondition",
+                "Actor/LifeCondition/Animal_Fish.blifecondition",
+            ],
+            [
+                "Physics/SupportBone/UMii_Goron_BodyC_X_000/UMii_Goron_BodyC_X_000.bphyssb",
+                "Physics/SupportBone/UMii_Goron_BodyC_X_000/UMii_Goron_BodyC_X_000.bphyssb",
+            ],
+            [
+                "Actor/RagdollBlendWeight/Dummy.brgbw",
+                "Actor/RagdollBlendWeight/Dummy.brgbw",
+            ],
+            [
+                "Actor/RagdollConfigList/Enemy_Guardian_Mini.brgconfiglist",
+                "Actor/RagdollConfigList/Enemy_Guardian_Mini.brgconfiglist",
+            ],
+        ] {
+            for ((platform, stock), name) in stock.iter().zip(smallest) {
+                let resource_type = super::ResourceType::from_name(name).unwrap();
+                assert_eq!(
+                    super::estimate_from_pio(&pio, resource_type, *platform),
+                    stock.get(name),
+                    "{name}"
+                );
+            }
+        }
+    }
+
+    /// The only ASSetting comes out at exactly its stock value when its entries
+    /// are left out, since its overhead is measured from it.
+    #[cfg(all(feature = "complex", feature = "botw-data"))]
+    #[test]
+    fn estimate_stock_assetting() {
+        use roead::aamp::ParameterIO;
+
+        use crate::ResourceSizeTable;
+
+        let pio = ParameterIO::new();
+        for platform in [Platform::WiiU, Platform::Switch] {
+            assert_eq!(
+                super::cpp_memsizes::bassetting::parse_pio_size(&pio, platform)
+                    .map(|parse_size| super::round_32(27960) + parse_size),
+                ResourceSizeTable::new_from_stock(platform).get("Actor/ASSetting.bassetting"),
+                "{platform:?}"
+            );
+        }
+    }
+
+    /// Checks the files in a stock actor pack against their stock values.
+    /// Parameter archives are the same on both platforms, so each is checked
+    /// against both tables.
+    #[cfg(all(feature = "complex", feature = "botw-data"))]
+    #[test]
+    fn estimate_pack_resources() {
+        use crate::ResourceSizeTable;
+
+        let pack = read("test/AirOcta_Tag.sbactorpack").unwrap();
+        let pack = super::decompress::yaz0(&pack).unwrap();
+        let files = super::sarc::files(&pack).unwrap();
+        for platform in [Platform::WiiU, Platform::Switch] {
+            let stock = ResourceSizeTable::new_from_stock(platform);
+            for name in [
+                "Actor/ActorLink/AirOcta_Tag.bxml",
+                "Actor/LifeCondition/Enemy.blifecondition",
+            ] {
+                let (_, bytes) = files.iter().find(|(file, _)| file == name).unwrap();
+                assert_ge!(
+                    super::estimate_from_slice_and_name(bytes, name, platform).unwrap(),
+                    stock.get(name).unwrap(),
+                    "{name}"
+                );
+            }
+        }
+    }
+
+    /// Actor links come out just above their stock values on both platforms,
+    /// by the margin kept in the overheads.
+    #[cfg(all(feature = "complex", feature = "botw-data"))]
+    #[test]
+    fn estimate_actor_links() {
+        use crate::ResourceSizeTable;
+
+        let pack = read("test/AirOcta_Tag.sbactorpack").unwrap();
+        let pack = super::decompress::yaz0(&pack).unwrap();
+        let files = super::sarc::files(&pack).unwrap();
+        let (_, air_octa) = files
+            .iter()
+            .find(|(file, _)| file == "Actor/ActorLink/AirOcta_Tag.bxml")
+            .unwrap();
+        // The fixture is the stock Enemy_Assassin_Senior link under a shorter
+        // name
+        let assassin = read("test/Assassin_Senior.bxml").unwrap();
+        for (platform, margin) in [(Platform::WiiU, 0x20), (Platform::Switch, 0x4)] {
+            let stock = ResourceSizeTable::new_from_stock(platform);
+            for (bytes, name) in [
+                (*air_octa, "Actor/ActorLink/AirOcta_Tag.bxml"),
+                (
+                    assassin.as_slice(),
+                    "Actor/ActorLink/Enemy_Assassin_Senior.bxml",
+                ),
+            ] {
+                assert_eq!(
+                    super::estimate_from_slice_and_name(bytes, name, platform),
+                    stock.get(name).map(|value| value + margin),
+                    "{name}"
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn estimate_aamp_text() {
+        use roead::aamp::ParameterIO;
+
+        let name = "Actor/Physics/Player_Link.bphysics";
+        let pio = ParameterIO::from_binary(read("test/Player_Link.bphysics").unwrap()).unwrap();
+        let text = pio.to_text();
+        for platform in [Platform::WiiU, Platform::Switch] {
+            let expected = super::estimate_from_pio(&pio, "bphysics", platform);
+            assert!(expected.is_some());
+            assert_eq!(
+                super::estimate_from_aamp_text(name, &text, platform),
+                expected
+            );
+            assert_eq!(
+                super::estimate_from_aamp_text(&format!("{name}.yml"), &text, platform),
+                expected
+            );
+        }
+        assert_eq!(
+            super::estimate_from_aamp_text(name, "not: [a, pio", Platform::WiiU),
+            None
+        );
+    }
+
+    #[test]
+    fn estimate_textures() {
+        // Unreadable archives fall back to the size heuristic when estimating,
+        // and to the plain calculation otherwise
+        let name = "Model/Broken.Tex1.bfres";
+        assert_eq!(
+            super::estimate_from_slice_and_name([0u8; 0x40], name, Platform::WiiU),
+            Some(super::estimate_bfres(0x40, Platform::WiiU))
+        );
+        assert_eq!(
+            super::calc_from_slice_and_name([0u8; 0x40], name, Platform::WiiU),
+            super::calc_from_size_and_name(0x40, name, Platform::WiiU)
+        );
     }
 
     #[cfg(feature = "complex")]
     #[test]
     fn estimate_sizes_complex() {
+        // Falls back to the size-only estimate when the file can't be parsed
+        assert_eq!(
+            super::estimate_from_slice_and_name(
+                [0xffu8; 0x40],
+                "Actor/AnimationInfo/Broken.baniminfo",
+                Endian::Big,
+            ),
+            Some(super::estimate_baniminfo(0x40, Platform::WiiU))
+        );
         assert_eq!(
             super::estimate_from_slice_and_name(
                 std::fs::read("test/Animal_Bass.Tex1.sbfres").unwrap(),
                 "Model/Animal_Bass.Tex1.sbfres",
                 Endian::Big
             ),
-            Some(42756)
+            Some(50692) // Stock is 50688
         );
-        assert_ge!(
+        assert_eq!(
             super::estimate_from_slice_and_name(
                 std::fs::read("test/DgnMrgPrt_Dungeon061.sbfres").unwrap(),
                 "Model/DgnMrgPrt_Dungeon061.sbfres",
                 Endian::Big,
             ),
-            Some(8671688)
+            Some(12026509) // Stock is 8671688
         );
         assert_ge!(
             super::estimate_from_slice_and_name(
@@ -726,6 +1255,19 @@ mod tests {
     }
     #[cfg(feature = "complex")]
     #[test]
+    fn baniminfo_size_tests() {
+        use std::mem::size_of;
+
+        use crate::calc::cpp_memsizes::cpp_classes::{AnimationInfo::*, Curve};
+        assert_eq!(size_of::<AnimInfo<u32>>(), 0x34);
+        assert_eq!(size_of::<AnimParamObj<u32>>(), 0x24);
+        assert_eq!(size_of::<AnimInfo<u64>>(), 0x68);
+        assert_eq!(size_of::<AnimParamObj<u64>>(), 0x40);
+        assert_eq!(size_of::<Curve<u32>>(), 0x84);
+        assert_eq!(size_of::<Curve<u64>>(), 0x88);
+    }
+    #[cfg(feature = "complex")]
+    #[test]
     fn baslist_size_tests() {
         use std::mem::size_of;
 
@@ -745,6 +1287,28 @@ mod tests {
     }
     #[cfg(feature = "complex")]
     #[test]
+    fn bassetting_size_tests() {
+        use std::mem::size_of;
+
+        use crate::calc::cpp_memsizes::cpp_classes::ASSetting::*;
+        assert_eq!(size_of::<BlenderBone<u32>>(), 0x84);
+        assert_eq!(size_of::<BoneParam<u32>>(), 0x54);
+        assert_eq!(size_of::<BlenderBone<u64>>(), 0xf8);
+        assert_eq!(size_of::<BoneParam<u64>>(), 0x98);
+    }
+    #[cfg(feature = "complex")]
+    #[test]
+    fn bbonectrl_size_tests() {
+        use std::mem::size_of;
+
+        use crate::calc::cpp_memsizes::cpp_classes::BoneControl::*;
+        assert_eq!(size_of::<BoneGroup<u32>>(), 0x7c);
+        assert_eq!(size_of::<Bone<u32>>(), 0x1c);
+        assert_eq!(size_of::<BoneGroup<u64>>(), 0xe0);
+        assert_eq!(size_of::<Bone<u64>>(), 0x30);
+    }
+    #[cfg(feature = "complex")]
+    #[test]
     fn bdrop_size_tests() {
         use std::mem::size_of;
 
@@ -919,6 +1483,21 @@ mod tests {
     }
     #[cfg(feature = "complex")]
     #[test]
+    fn blifecondition_size_tests() {
+        use std::mem::size_of;
+
+        use crate::calc::cpp_memsizes::cpp_classes::LifeCondition::*;
+        assert_eq!(size_of::<ConditionList<u32>>(), 0x24);
+        assert_eq!(size_of::<ConditionItem<u32>>(), 0x1c);
+        assert_eq!(size_of::<DisplayDistance<u32>>(), 0x2c);
+        assert_eq!(size_of::<YLimit<u32>>(), 0x34);
+        assert_eq!(size_of::<ConditionList<u64>>(), 0x40);
+        assert_eq!(size_of::<ConditionItem<u64>>(), 0x30);
+        assert_eq!(size_of::<DisplayDistance<u64>>(), 0x50);
+        assert_eq!(size_of::<YLimit<u64>>(), 0x58);
+    }
+    #[cfg(feature = "complex")]
+    #[test]
     fn bmodellist_size_tests() {
         use std::mem::size_of;
 
@@ -966,6 +1545,10 @@ mod tests {
         assert_eq!(size_of::<CollisionInfoParam<u64>>(), 0xb0);
         assert_eq!(size_of::<EdgeRigidBodyParam<u64>>(), 0xa8);
         assert_eq!(size_of::<ShapeParamObj<u64>>(), 0x278);
+        assert_eq!(size_of::<SupportBoneMainBone<u32>>(), 0x78);
+        assert_eq!(size_of::<SupportBoneData<u32>>(), 0x90);
+        assert_eq!(size_of::<SupportBoneMainBone<u64>>(), 0xd8);
+        assert_eq!(size_of::<SupportBoneData<u64>>(), 0xf8);
     }
     #[cfg(feature = "complex")]
     #[test]
@@ -980,563 +1563,36 @@ mod tests {
     }
     #[cfg(feature = "complex")]
     #[test]
-    fn bshop_size_tests() {
+    fn brgbw_size_tests() {
         use std::mem::size_of;
 
-        use crate::calc::cpp_memsizes::cpp_classes::ShopData::*;
-        assert_eq!(size_of::<Table<u32>>(), 0x4c);
-        assert_eq!(size_of::<Item<u32>>(), 0x68);
-        assert_eq!(size_of::<Table<u64>>(), 0x88);
-        assert_eq!(size_of::<Item<u64>>(), 0xc8);
-    }
-    #[cfg(feature = "complex_testing")]
-    #[test]
-    fn test_all_baiprog() {
-        use std::collections::HashSet;
-
-        use glob::glob;
-        use roead::{aamp::ParameterIO, sarc};
-
-        use crate::ResourceSizeTable;
-        let mut result: HashSet<String> = HashSet::new();
-
-        let root = "E:/Users/chodn/Documents/ISOs - WiiU/The Legend of Zelda Breath of the Wild \
-                    (UPDATE DATA) (v208) (USA)/content";
-        let rstb_path = root.to_owned() + "/System/Resource/ResourceSizeTable.product.srsizetable";
-        let rstable = ResourceSizeTable::from_binary(std::fs::read(rstb_path).unwrap()).unwrap();
-        for entry in glob(&(root.to_owned() + "/Actor/Pack/*.sbactorpack")).unwrap() {
-            match entry {
-                Ok(path) => {
-                    let actorname = path.file_stem().unwrap().to_str().unwrap();
-                    let sarc = sarc::Sarc::new(std::fs::read(&path).unwrap()).unwrap();
-                    let bxml = ParameterIO::from_binary(
-                        sarc.get_data(&format!("Actor/ActorLink/{}.bxml", actorname))
-                            .unwrap(),
-                    )
-                    .unwrap();
-                    let user = bxml
-                        .param_root
-                        .objects
-                        .get("LinkTarget")
-                        .unwrap()
-                        .get("AIProgramUser")
-                        .unwrap()
-                        .as_str()
-                        .unwrap();
-                    let param_name = format!("Actor/AIProgram/{}.baiprog", user);
-                    if param_name.contains("Dummy") | result.contains(&param_name) {
-                        continue;
-                    }
-                    if let Some(o_file) = sarc.get_data(&param_name) {
-                        if let Some(rstb_entry) = rstable.get(param_name.as_str()) {
-                            let calc_size = super::estimate_from_bytes_and_name(
-                                o_file,
-                                &param_name,
-                                Endian::Big,
-                            )
-                            .unwrap();
-                            assert_ge!(calc_size, rstb_entry);
-                            result.insert(param_name);
-                        } else {
-                            println!("{} not in RSTB???", &param_name);
-                            continue;
-                        }
-                    }
-                }
-                Err(_) => println!("File error...?"),
-            }
-        }
-    }
-    #[cfg(feature = "complex_testing")]
-    #[test]
-    fn test_all_baslist() {
-        use std::collections::HashSet;
-
-        use glob::glob;
-        use roead::{aamp::ParameterIO, sarc};
-
-        use crate::ResourceSizeTable;
-        let mut result: HashSet<String> = HashSet::new();
-
-        let root = "E:/Users/chodn/Documents/ISOs - WiiU/The Legend of Zelda Breath of the Wild \
-                    (UPDATE DATA) (v208) (USA)/content";
-        let rstb_path = root.to_owned() + "/System/Resource/ResourceSizeTable.product.srsizetable";
-        let rstable = ResourceSizeTable::from_binary(std::fs::read(rstb_path).unwrap()).unwrap();
-        for entry in glob(&(root.to_owned() + "/Actor/Pack/*.sbactorpack")).unwrap() {
-            match entry {
-                Ok(path) => {
-                    let actorname = path.file_stem().unwrap().to_str().unwrap();
-                    let sarc = sarc::Sarc::new(std::fs::read(&path).unwrap()).unwrap();
-                    let bxml = ParameterIO::from_binary(
-                        sarc.get_data(&format!("Actor/ActorLink/{}.bxml", actorname))
-                            .unwrap(),
-                    )
-                    .unwrap();
-                    let user = bxml
-                        .param_root
-                        .objects
-                        .get("LinkTarget")
-                        .unwrap()
-                        .get("ASUser")
-                        .unwrap()
-                        .as_str()
-                        .unwrap();
-                    let param_name = format!("Actor/ASList/{}.baslist", user);
-                    if param_name.contains("Dummy") | result.contains(&param_name) {
-                        continue;
-                    }
-                    if let Some(o_file) = sarc.get_data(&param_name) {
-                        if let Some(rstb_entry) = rstable.get(param_name.as_str()) {
-                            let calc_size = super::estimate_from_bytes_and_name(
-                                o_file,
-                                &param_name,
-                                Endian::Big,
-                            )
-                            .unwrap();
-                            assert_ge!(calc_size, rstb_entry);
-                            result.insert(param_name);
-                        } else {
-                            println!("{} not in RSTB???", &param_name);
-                            continue;
-                        }
-                    }
-                }
-                Err(_) => println!("File error...?"),
-            }
-        }
-    }
-    #[cfg(feature = "complex_testing")]
-    #[test]
-    fn test_all_bdrop() {
-        use std::collections::HashSet;
-
-        use glob::glob;
-        use roead::{aamp::ParameterIO, sarc};
-
-        use crate::ResourceSizeTable;
-        let mut result: HashSet<String> = HashSet::new();
-
-        let root = "E:/Users/chodn/Documents/ISOs - WiiU/The Legend of Zelda Breath of the Wild \
-                    (UPDATE DATA) (v208) (USA)/content";
-        let rstb_path = root.to_owned() + "/System/Resource/ResourceSizeTable.product.srsizetable";
-        let rstable = ResourceSizeTable::from_binary(std::fs::read(rstb_path).unwrap()).unwrap();
-        for entry in glob(&(root.to_owned() + "/Actor/Pack/*.sbactorpack")).unwrap() {
-            match entry {
-                Ok(path) => {
-                    let actorname = path.file_stem().unwrap().to_str().unwrap();
-                    let sarc = sarc::Sarc::new(std::fs::read(&path).unwrap()).unwrap();
-                    let bxml = ParameterIO::from_binary(
-                        sarc.get_data(&format!("Actor/ActorLink/{}.bxml", actorname))
-                            .unwrap(),
-                    )
-                    .unwrap();
-                    let user = bxml
-                        .param_root
-                        .objects
-                        .get("LinkTarget")
-                        .unwrap()
-                        .get("DropTableUser")
-                        .unwrap()
-                        .as_str()
-                        .unwrap();
-                    let param_name = format!("Actor/DropTable/{}.bdrop", user);
-                    if param_name.contains("Dummy") | result.contains(&param_name) {
-                        continue;
-                    }
-                    if let Some(o_file) = sarc.get_data(&param_name) {
-                        if let Some(rstb_entry) = rstable.get(param_name.as_str()) {
-                            let calc_size = super::estimate_from_bytes_and_name(
-                                o_file,
-                                &param_name,
-                                Endian::Big,
-                            )
-                            .unwrap();
-                            assert_ge!(calc_size, rstb_entry);
-                            result.insert(param_name);
-                        } else {
-                            println!("{} not in RSTB???", &param_name);
-                            continue;
-                        }
-                    }
-                }
-                Err(_) => println!("File error...?"),
-            }
-        }
-    }
-    #[cfg(feature = "complex_testing")]
-    #[test]
-    fn test_all_bgparamlist() {
-        use std::collections::HashSet;
-
-        use glob::glob;
-        use roead::{aamp::ParameterIO, sarc};
-
-        use crate::ResourceSizeTable;
-        let mut result: HashSet<String> = HashSet::new();
-
-        let root = "E:/Users/chodn/Documents/ISOs - WiiU/The Legend of Zelda Breath of the Wild \
-                    (UPDATE DATA) (v208) (USA)/content";
-        let rstb_path = root.to_owned() + "/System/Resource/ResourceSizeTable.product.srsizetable";
-        let rstable = ResourceSizeTable::from_binary(std::fs::read(rstb_path).unwrap()).unwrap();
-        for entry in glob(&(root.to_owned() + "/Actor/Pack/*.sbactorpack")).unwrap() {
-            match entry {
-                Ok(path) => {
-                    let actorname = path.file_stem().unwrap().to_str().unwrap();
-                    let sarc = sarc::Sarc::new(std::fs::read(&path).unwrap()).unwrap();
-                    let bxml = ParameterIO::from_binary(
-                        sarc.get_data(&format!("Actor/ActorLink/{}.bxml", actorname))
-                            .unwrap(),
-                    )
-                    .unwrap();
-                    let user = bxml
-                        .param_root
-                        .objects
-                        .get("LinkTarget")
-                        .unwrap()
-                        .get("GParamUser")
-                        .unwrap()
-                        .as_str()
-                        .unwrap();
-                    let param_name = format!("Actor/GeneralParamList/{}.bgparamlist", user);
-                    if param_name.contains("Dummy") | result.contains(&param_name) {
-                        continue;
-                    }
-                    if let Some(o_file) = sarc.get_data(&param_name) {
-                        if let Some(rstb_entry) = rstable.get(param_name.as_str()) {
-                            let calc_size = super::estimate_from_bytes_and_name(
-                                o_file,
-                                &param_name,
-                                Endian::Big,
-                            )
-                            .unwrap();
-                            assert_ge!(calc_size, rstb_entry);
-                            result.insert(param_name);
-                        } else {
-                            println!("{} not in RSTB???", &param_name);
-                            continue;
-                        }
-                    }
-                }
-                Err(_) => println!("File error...?"),
-            }
-        }
-    }
-    #[cfg(feature = "complex_testing")]
-    #[test]
-    fn test_all_bmodellist() {
-        use std::collections::HashSet;
-
-        use glob::glob;
-        use roead::{aamp::ParameterIO, sarc};
-
-        use crate::ResourceSizeTable;
-        let mut result: HashSet<String> = HashSet::new();
-
-        let root = "E:/Users/chodn/Documents/ISOs - WiiU/The Legend of Zelda Breath of the Wild \
-                    (UPDATE DATA) (v208) (USA)/content";
-        let rstb_path = root.to_owned() + "/System/Resource/ResourceSizeTable.product.srsizetable";
-        let rstable = ResourceSizeTable::from_binary(std::fs::read(rstb_path).unwrap()).unwrap();
-        for entry in glob(&(root.to_owned() + "/Actor/Pack/*.sbactorpack")).unwrap() {
-            match entry {
-                Ok(path) => {
-                    let actorname = path.file_stem().unwrap().to_str().unwrap();
-                    let sarc = sarc::Sarc::new(std::fs::read(&path).unwrap()).unwrap();
-                    let bxml = ParameterIO::from_binary(
-                        sarc.get_data(&format!("Actor/ActorLink/{}.bxml", actorname))
-                            .unwrap(),
-                    )
-                    .unwrap();
-                    let user = bxml
-                        .param_root
-                        .objects
-                        .get("LinkTarget")
-                        .unwrap()
-                        .get("ModelUser")
-                        .unwrap()
-                        .as_str()
-                        .unwrap();
-                    let param_name = format!("Actor/ModelList/{}.bmodellist", user);
-                    if param_name.contains("Dummy") | result.contains(&param_name) {
-                        continue;
-                    }
-                    if let Some(o_file) = sarc.get_data(&param_name) {
-                        if let Some(rstb_entry) = rstable.get(param_name.as_str()) {
-                            let calc_size = super::estimate_from_bytes_and_name(
-                                o_file,
-                                &param_name,
-                                Endian::Big,
-                            )
-                            .unwrap();
-                            assert_ge!(calc_size, rstb_entry);
-                            result.insert(param_name);
-                        } else {
-                            println!("{} not in RSTB???", &param_name);
-                            continue;
-                        }
-                    }
-                }
-                Err(_) => println!("File error...?"),
-            }
-        }
-    }
-    #[cfg(feature = "complex_testing")]
-    #[test]
-    fn test_all_bphysics() {
-        use std::collections::HashSet;
-
-        use glob::glob;
-        use roead::{aamp::ParameterIO, sarc};
-
-        use crate::ResourceSizeTable;
-        let mut result: HashSet<String> = HashSet::new();
-
-        let root = "E:/Users/chodn/Documents/ISOs - WiiU/The Legend of Zelda Breath of the Wild \
-                    (UPDATE DATA) (v208) (USA)/content";
-        let rstb_path = root.to_owned() + "/System/Resource/ResourceSizeTable.product.srsizetable";
-        let rstable = ResourceSizeTable::from_binary(std::fs::read(rstb_path).unwrap()).unwrap();
-        for entry in glob(&(root.to_owned() + "/Actor/Pack/*.sbactorpack")).unwrap() {
-            match entry {
-                Ok(path) => {
-                    let actorname = path.file_stem().unwrap().to_str().unwrap();
-                    let sarc = sarc::Sarc::new(std::fs::read(&path).unwrap()).unwrap();
-                    let bxml = ParameterIO::from_binary(
-                        sarc.get_data(&format!("Actor/ActorLink/{}.bxml", actorname))
-                            .unwrap(),
-                    )
-                    .unwrap();
-                    let user = bxml
-                        .param_root
-                        .objects
-                        .get("LinkTarget")
-                        .unwrap()
-                        .get("PhysicsUser")
-                        .unwrap()
-                        .as_str()
-                        .unwrap();
-                    let param_name = format!("Actor/Physics/{}.bphysics", user);
-                    if param_name.contains("Dummy") | result.contains(&param_name) {
-                        continue;
-                    }
-                    if let Some(o_file) = sarc.get_data(&param_name) {
-                        if let Some(rstb_entry) = rstable.get(param_name.as_str()) {
-                            let calc_size = super::estimate_from_bytes_and_name(
-                                o_file,
-                                &param_name,
-                                Endian::Big,
-                            )
-                            .unwrap();
-                            assert_ge!(calc_size, rstb_entry);
-                            result.insert(param_name);
-                        } else {
-                            println!("{} not in RSTB???", &param_name);
-                            continue;
-                        }
-                    }
-                }
-                Err(_) => println!("File error...?"),
-            }
-        }
-    }
-    #[cfg(feature = "complex_testing")]
-    #[test]
-    fn test_all_brecipe() {
-        use std::collections::HashSet;
-
-        use glob::glob;
-        use roead::{aamp::ParameterIO, sarc};
-
-        use crate::ResourceSizeTable;
-        let mut result: HashSet<String> = HashSet::new();
-
-        let root = "E:/Users/chodn/Documents/ISOs - WiiU/The Legend of Zelda Breath of the Wild \
-                    (UPDATE DATA) (v208) (USA)/content";
-        let rstb_path = root.to_owned() + "/System/Resource/ResourceSizeTable.product.srsizetable";
-        let rstable = ResourceSizeTable::from_binary(std::fs::read(rstb_path).unwrap()).unwrap();
-        for entry in glob(&(root.to_owned() + "/Actor/Pack/*.sbactorpack")).unwrap() {
-            match entry {
-                Ok(path) => {
-                    let actorname = path.file_stem().unwrap().to_str().unwrap();
-                    let sarc = sarc::Sarc::new(std::fs::read(&path).unwrap()).unwrap();
-                    let bxml = ParameterIO::from_binary(
-                        sarc.get_data(&format!("Actor/ActorLink/{}.bxml", actorname))
-                            .unwrap(),
-                    )
-                    .unwrap();
-                    let user = bxml
-                        .param_root
-                        .objects
-                        .get("LinkTarget")
-                        .unwrap()
-                        .get("RecipeUser")
-                        .unwrap()
-                        .as_str()
-                        .unwrap();
-                    let param_name = format!("Actor/Recipe/{}.brecipe", user);
-                    if param_name.contains("Dummy") | result.contains(&param_name) {
-                        continue;
-                    }
-                    if let Some(o_file) = sarc.get_data(&param_name) {
-                        if let Some(rstb_entry) = rstable.get(param_name.as_str()) {
-                            let calc_size = super::estimate_from_bytes_and_name(
-                                o_file,
-                                &param_name,
-                                Endian::Big,
-                            )
-                            .unwrap();
-                            assert_ge!(calc_size, rstb_entry);
-                            result.insert(param_name);
-                        } else {
-                            println!("{} not in RSTB???", &param_name);
-                            continue;
-                        }
-                    }
-                }
-                Err(_) => println!("File error...?"),
-            }
-        }
+        use crate::calc::cpp_memsizes::cpp_classes::RagdollBlendWeight::*;
+        assert_eq!(size_of::<State<u32>>(), 0x70);
+        assert_eq!(size_of::<InputWeight<u32>>(), 0x44);
+        assert_eq!(size_of::<State<u64>>(), 0xd0);
+        assert_eq!(size_of::<InputWeight<u64>>(), 0x78);
     }
-    #[cfg(feature = "complex_testing")]
-    #[test]
-    fn test_all_bshop() {
-        use std::collections::HashSet;
-
-        use glob::glob;
-        use roead::{aamp::ParameterIO, sarc};
-
-        use crate::ResourceSizeTable;
-        let mut result: HashSet<String> = HashSet::new();
-
-        let root = "E:/Users/chodn/Documents/ISOs - WiiU/The Legend of Zelda Breath of the Wild \
-                    (UPDATE DATA) (v208) (USA)/content";
-        let rstb_path = root.to_owned() + "/System/Resource/ResourceSizeTable.product.srsizetable";
-        let rstable = ResourceSizeTable::from_binary(std::fs::read(rstb_path).unwrap()).unwrap();
-        for entry in glob(&(root.to_owned() + "/Actor/Pack/*.sbactorpack")).unwrap() {
-            match entry {
-                Ok(path) => {
-                    let actorname = path.file_stem().unwrap().to_str().unwrap();
-                    let sarc = sarc::Sarc::new(std::fs::read(&path).unwrap()).unwrap();
-                    let bxml = ParameterIO::from_binary(
-                        sarc.get_data(&format!("Actor/ActorLink/{}.bxml", actorname))
-                            .unwrap(),
-                    )
-                    .unwrap();
-                    let user = bxml
-                        .param_root
-                        .objects
-                        .get("LinkTarget")
-                        .unwrap()
-                        .get("ShopDataUser")
-                        .unwrap()
-                        .as_str()
-                        .unwrap();
-                    let param_name = format!("Actor/ShopData/{}.bshop", user);
-                    if param_name.contains("Dummy") | result.contains(&param_name) {
-                        continue;
-                    }
-                    if let Some(o_file) = sarc.get_data(&param_name) {
-                        if let Some(rstb_entry) = rstable.get(param_name.as_str()) {
-                            let calc_size = super::estimate_from_bytes_and_name(
-                                o_file,
-                                &param_name,
-                                Endian::Big,
-                            )
-                            .unwrap();
-                            assert_ge!(calc_size, rstb_entry);
-                            result.insert(param_name);
-                        } else {
-                            println!("{} not in RSTB???", &param_name);
-                            continue;
-                        }
-                    }
-                }
-                Err(_) => println!("File error...?"),
-            }
-        }
-    }
-    #[cfg(feature = "complex_testing")]
+    #[cfg(feature = "complex")]
     #[test]
-    fn test_all_bxml() {
-        use std::collections::HashSet;
-
-        use glob::glob;
-        use roead::sarc;
-
-        use crate::ResourceSizeTable;
-        let mut result: HashSet<String> = HashSet::new();
+    fn brgconfiglist_size_tests() {
+        use std::mem::size_of;
 
-        let root = "E:/Users/chodn/Documents/ISOs - WiiU/The Legend of Zelda Breath of the Wild \
-                    (UPDATE DATA) (v208) (USA)/content";
-        let rstb_path = root.to_owned() + "/System/Resource/ResourceSizeTable.product.srsizetable";
-        let rstable = ResourceSizeTable::from_binary(std::fs::read(rstb_path).unwrap()).unwrap();
-        for entry in glob(&(root.to_owned() + "/Actor/Pack/*.sbactorpack")).unwrap() {
-            match entry {
-                Ok(path) => {
-                    let actorname = path.file_stem().unwrap().to_str().unwrap();
-                    let sarc = sarc::Sarc::new(std::fs::read(&path).unwrap()).unwrap();
-                    let param_name = format!("Actor/ActorLink/{}.bxml", actorname);
-                    if param_name.contains("Dummy") | result.contains(&param_name) {
-                        continue;
-                    }
-                    let bxml_bytes = sarc.get_data(&param_name).unwrap();
-                    if let Some(rstb_entry) = rstable.get(param_name.as_str()) {
-                        let calc_size = super::estimate_from_bytes_and_name(
-                            bxml_bytes,
-                            &param_name,
-                            Endian::Big,
-                        )
-                        .unwrap();
-                        assert_ge!(calc_size, rstb_entry);
-                        result.insert(param_name);
-                    } else {
-                        println!("{} not in RSTB???", &param_name);
-                        continue;
-                    }
-                }
-                Err(_) => println!("File error...?"),
-            }
-        }
+        use crate::calc::cpp_memsizes::cpp_classes::RagdollConfigList::*;
+        assert_eq!(size_of::<ImpulseParam<u32>>(), 0x50);
+        assert_eq!(size_of::<BodyParam<u32>>(), 0x54);
+        assert_eq!(size_of::<ImpulseParam<u64>>(), 0x88);
+        assert_eq!(size_of::<BodyParam<u64>>(), 0x98);
     }
-    #[cfg(feature = "complex_testing")]
+    #[cfg(feature = "complex")]
     #[test]
-    fn test_all_bxml_nx() {
-        use std::collections::HashSet;
-
-        use glob::glob;
-        use roead::sarc;
-
-        use crate::ResourceSizeTable;
-        let mut result: HashSet<String> = HashSet::new();
+    fn bshop_size_tests() {
+        use std::mem::size_of;
 
-        let root = "E:/Users/chodn/Documents/ISOs - Switch/LoZBOTW/content";
-        let rstb_path = root.to_owned() + "/System/Resource/ResourceSizeTable.product.srsizetable";
-        let rstable = ResourceSizeTable::from_binary(std::fs::read(rstb_path).unwrap()).unwrap();
-        for entry in glob(&(root.to_owned() + "/Actor/Pack/*.sbactorpack")).unwrap() {
-            match entry {
-                Ok(path) => {
-                    let actorname = path.file_stem().unwrap().to_str().unwrap();
-                    let sarc = sarc::Sarc::new(std::fs::read(&path).unwrap()).unwrap();
-                    let param_name = format!("Actor/ActorLink/{}.bxml", actorname);
-                    if param_name.contains("Dummy") | result.contains(&param_name) {
-                        continue;
-                    }
-                    let bxml_bytes = sarc.get_data(&param_name).unwrap();
-                    if let Some(rstb_entry) = rstable.get(param_name.as_str()) {
-                        let calc_size = super::estimate_from_bytes_and_name(
-                            bxml_bytes,
-                            &param_name,
-                            Endian::Little,
-                        )
-                        .unwrap();
-                        assert_ge!(calc_size, rstb_entry);
-                        result.insert(param_name);
-                    } else {
-                        println!("{} not in RSTB???", &param_name);
-                        continue;
-                    }
-                }
-                Err(_) => println!("File error...?"),
-            }
-        }
+        use crate::calc::cpp_memsizes::cpp_classes::ShopData::*;
+        assert_eq!(size_of::<Table<u32>>(), 0x4c);
+        assert_eq!(size_of::<Item<u32>>(), 0x68);
+        assert_eq!(size_of::<Table<u64>>(), 0x88);
+        assert_eq!(size_of::<Item<u64>>(), 0xc8);
     }
     #[cfg(feature = "complex_testing")]
     #[test]
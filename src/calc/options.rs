@@ -0,0 +1,215 @@
+//! Options for trading RSTB value safety against wasted heap in estimates.
+
+use super::{
+    calc_from_size_and_name, calc_from_slice_and_name, data_size, estimate_from_bytes_and_name,
+    estimate_from_size_and_name, resource_ext,
+};
+#[cfg(feature = "complex")]
+use super::{cpp_memsizes::bphysics, round_32};
+use crate::Platform;
+
+/// How close to the true value an estimate should aim.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EstimateMode {
+    /// Aim for the smallest value known to work for stock files. This can
+    /// crash the game on unusual modded files.
+    Tight,
+    /// The behaviour of the plain `estimate` functions.
+    #[default]
+    Balanced,
+    /// Never go below the balanced estimate, the estimate from the file size
+    /// alone or, with the `botw-data` feature, the resource's stock value,
+    /// and use the most cautious bphysics overhead. These are floors, not an
+    /// upper bound on what the game needs: a modded file unlike any measured
+    /// can still come out too low.
+    Conservative,
+}
+
+/// Options for the `estimate_*_with_options` functions.
+///
+/// Infallible calculations are exact, so neither the mode nor the margin
+/// affects them, but the stock floor applies to every value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EstimateOptions {
+    pub mode: EstimateMode,
+    /// Extra space added to estimated values, as a fraction of the value
+    /// (e.g. `0.05` for 5%).
+    pub margin: f32,
+    /// Extra space added to estimated values, in bytes, after the fractional
+    /// margin.
+    pub margin_bytes: u32,
+    /// *Requires the `botw-data` feature.*
+    /// Never return a value lower than the resource's value in the stock
+    /// RSTB for the platform. Always on in conservative mode.
+    #[cfg(feature = "botw-data")]
+    pub stock_floor: bool,
+}
+
+impl Default for EstimateOptions {
+    fn default() -> Self {
+        Self {
+            mode: EstimateMode::Balanced,
+            margin: 0.0,
+            margin_bytes: 0,
+            #[cfg(feature = "botw-data")]
+            stock_floor: false,
+        }
+    }
+}
+
+impl EstimateOptions {
+    /// Options for tight estimates with no margin.
+    pub fn tight() -> Self {
+        Self {
+            mode: EstimateMode::Tight,
+            ..Default::default()
+        }
+    }
+
+    /// Options for conservative estimates with no margin.
+    pub fn conservative() -> Self {
+        Self {
+            mode: EstimateMode::Conservative,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn estimate_bytes(
+        &self,
+        bytes: &[u8],
+        name: &str,
+        platform: Platform,
+    ) -> Option<u32> {
+        let value = match calc_from_slice_and_name(bytes, name, platform) {
+            Some(value) => value,
+            None => {
+                let filesize = data_size(bytes);
+                let value = self.adjust(
+                    estimate_from_bytes_and_name(bytes, name, platform)?,
+                    bytes,
+                    filesize,
+                    name,
+                    platform,
+                );
+                let value = match self.mode {
+                    EstimateMode::Conservative => {
+                        estimate_from_size_and_name(filesize, name, platform)
+                            .map_or(value, |by_size| value.max(by_size))
+                    }
+                    _ => value,
+                };
+                self.apply_margin(value)
+            }
+        };
+        Some(self.apply_floor(value, name, platform))
+    }
+
+    pub(crate) fn estimate_size(
+        &self,
+        filesize: usize,
+        name: &str,
+        platform: Platform,
+    ) -> Option<u32> {
+        let value = match calc_from_size_and_name(filesize, name, platform) {
+            Some(value) => value,
+            None => self.apply_margin(estimate_from_size_and_name(filesize, name, platform)?),
+        };
+        Some(self.apply_floor(value, name, platform))
+    }
+
+    /// Swaps the overheads used by the parsers for the ones of the mode. Only
+    /// values the parser produced are changed, not ones from the size-only
+    /// fallback or a custom calculator.
+    #[cfg_attr(not(feature = "complex"), allow(unused_variables))]
+    fn adjust(
+        &self,
+        value: u32,
+        bytes: &[u8],
+        filesize: usize,
+        name: &str,
+        platform: Platform,
+    ) -> u32 {
+        match resource_ext(name) {
+            #[cfg(feature = "complex")]
+            Some("bphysics")
+                if bphysics::parse_size(bytes, platform)
+                    .is_some_and(|size| round_32(filesize) + size == value) =>
+            {
+                match self.mode {
+                    EstimateMode::Tight => {
+                        value.saturating_sub(
+                            bphysics::PARAMSET_OVERHEAD - bphysics::PARAMSET_OVERHEAD_TIGHT,
+                        )
+                    }
+                    EstimateMode::Balanced => value,
+                    EstimateMode::Conservative => {
+                        value.saturating_add(
+                            bphysics::PARAMSET_OVERHEAD_CONSERVATIVE - bphysics::PARAMSET_OVERHEAD,
+                        )
+                    }
+                }
+            }
+            _ => value,
+        }
+    }
+
+    fn apply_margin(&self, value: u32) -> u32 {
+        // Float to integer casts saturate, so only the byte margin can
+        // overflow
+        ((value as f64 * (1.0 + self.margin.max(0.0) as f64)).ceil() as u32)
+            .saturating_add(self.margin_bytes)
+    }
+
+    #[cfg_attr(not(feature = "botw-data"), allow(unused_variables))]
+    fn apply_floor(&self, value: u32, name: &str, platform: Platform) -> u32 {
+        #[cfg(feature = "botw-data")]
+        if self.stock_floor || self.mode == EstimateMode::Conservative {
+            let stock = match platform {
+                Platform::WiiU => &*crate::botw::WIIU_RSTB,
+                Platform::Switch => &*crate::botw::SWITCH_RSTB,
+            };
+            let canonical = resource_ext(name)
+                .and_then(|ext| Some(format!("{}{}", &name[..name.find('.')? + 1], ext)));
+            if let Some(stock_value) = stock.get(name).or_else(|| stock.get(canonical.as_deref()?))
+            {
+                return value.max(stock_value);
+            }
+        }
+        value
+    }
+}
+
+#[cfg(all(test, feature = "complex"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjust_parsed_only() {
+        let bytes = std::fs::read("test/Player_Link.bphysics").unwrap();
+        let name = "Actor/Physics/Player_Link.bphysics";
+        let parsed = estimate_from_bytes_and_name(&bytes, name, Platform::WiiU).unwrap();
+        let by_size = estimate_from_size_and_name(bytes.len(), name, Platform::WiiU).unwrap();
+        let tight = EstimateOptions::tight();
+        assert_eq!(
+            tight.adjust(parsed, &bytes, bytes.len(), name, Platform::WiiU),
+            parsed - (bphysics::PARAMSET_OVERHEAD - bphysics::PARAMSET_OVERHEAD_TIGHT)
+        );
+        // A value from anywhere but the parser is left alone
+        for options in [tight, EstimateOptions::conservative()] {
+            assert_eq!(
+                options.adjust(by_size, &bytes, bytes.len(), name, Platform::WiiU),
+                by_size
+            );
+        }
+    }
+    #[test]
+    fn margin_saturates() {
+        let options = EstimateOptions {
+            margin: 0.5,
+            margin_bytes: u32::MAX,
+            ..Default::default()
+        };
+        assert_eq!(options.apply_margin(0x100), u32::MAX);
+        assert_eq!(options.apply_margin(u32::MAX), u32::MAX);
+    }
+}
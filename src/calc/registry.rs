@@ -0,0 +1,336 @@
+//! Per-type calculators and the registry the calculation functions dispatch
+//! through.
+//!
+//! Every resource type is handled by a [`Calculator`]. The default registry
+//! maps all built-in types to [`BuiltinCalculator`], and any type can be added
+//! or overridden, either on a [`CalculatorRegistry`] used directly or on the
+//! default registry with [`register_calculator()`], which the free `calc_*`
+//! and `estimate_*` functions use.
+//!
+//! ```
+//! use rstb::{
+//!     calc::{self, BuiltinCalculator, Calculator},
+//!     Platform,
+//! };
+//!
+//! // A researched format with a fixed overhead
+//! struct MyFormat;
+//!
+//! impl Calculator for MyFormat {
+//!     fn calc_size(&self, filesize: usize, name: &str, platform: Platform) -> Option<u32> {
+//!         Some(BuiltinCalculator.calc_size(filesize, name, platform)? + 0x100)
+//!     }
+//! }
+//!
+//! calc::register_calculator("bmyformat", MyFormat);
+//! assert_eq!(
+//!     calc::calc_from_size_and_name(1000, "Test/Example.bmyformat", Platform::WiiU),
+//!     Some(1024 + 0xe4 + 0x20 + 0x100)
+//! );
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use super::{builtin_from_bytes_and_name, builtin_from_size_and_name, info, resource_ext};
+use crate::Platform;
+
+/// A resource type as used for calculations: the file extension after the
+/// first dot of its name, without the Yaz0 `s` prefix (e.g. `bfres`,
+/// `Tex1.bfres`, `bactorpack`).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResourceType(String);
+
+impl ResourceType {
+    /// Gets the resource type of a file name, returning `None` if it has no
+    /// extension.
+    pub fn from_name(name: &str) -> Option<Self> {
+        resource_ext(name).map(Self::from)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for ResourceType {
+    fn from(ext: &str) -> Self {
+        Self(ext.to_owned())
+    }
+}
+
+impl From<String> for ResourceType {
+    fn from(ext: String) -> Self {
+        Self(ext)
+    }
+}
+
+impl fmt::Display for ResourceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Calculates RSTB values for a resource type. All methods have defaults:
+/// the `estimate` methods fall back to the matching `calc` method, and the
+/// `calc` methods return `None`, so an implementation only needs to provide
+/// what it supports.
+///
+/// Calculators are called while the default registry is locked, so they must
+/// not call the free calculation functions or [`register_calculator()`].
+/// Call [`BuiltinCalculator`] directly to reuse the built-in behaviour.
+pub trait Calculator: Send + Sync {
+    /// Infallibly calculate a value from a file's bytes, which may be Yaz0
+    /// compressed.
+    fn calc(&self, bytes: &[u8], name: &str, platform: Platform) -> Option<u32> {
+        let _ = (bytes, name, platform);
+        None
+    }
+
+    /// Calculate or estimate a value from a file's bytes, which may be Yaz0
+    /// compressed.
+    fn estimate(&self, bytes: &[u8], name: &str, platform: Platform) -> Option<u32> {
+        self.calc(bytes, name, platform)
+    }
+
+    /// Infallibly calculate a value from a file's uncompressed size.
+    fn calc_size(&self, filesize: usize, name: &str, platform: Platform) -> Option<u32> {
+        let _ = (filesize, name, platform);
+        None
+    }
+
+    /// Calculate or estimate a value from a file's uncompressed size.
+    fn estimate_size(&self, filesize: usize, name: &str, platform: Platform) -> Option<u32> {
+        self.calc_size(filesize, name, platform)
+    }
+}
+
+/// The calculations built into this crate, including the `complex` parsers
+/// when that feature is enabled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinCalculator;
+
+impl Calculator for BuiltinCalculator {
+    fn calc(&self, bytes: &[u8], name: &str, platform: Platform) -> Option<u32> {
+        builtin_from_bytes_and_name(bytes, name, platform, false)
+    }
+
+    fn estimate(&self, bytes: &[u8], name: &str, platform: Platform) -> Option<u32> {
+        builtin_from_bytes_and_name(bytes, name, platform, true)
+    }
+
+    fn calc_size(&self, filesize: usize, name: &str, platform: Platform) -> Option<u32> {
+        builtin_from_size_and_name(filesize, name, platform, false)
+    }
+
+    fn estimate_size(&self, filesize: usize, name: &str, platform: Platform) -> Option<u32> {
+        builtin_from_size_and_name(filesize, name, platform, true)
+    }
+}
+
+/// Maps resource types to calculators. Types without a calculator of their
+/// own use the fallback, which defaults to [`BuiltinCalculator`].
+#[derive(Clone)]
+pub struct CalculatorRegistry {
+    calculators: BTreeMap<ResourceType, Arc<dyn Calculator>>,
+    fallback:    Arc<dyn Calculator>,
+}
+
+impl fmt::Debug for CalculatorRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CalculatorRegistry")
+            .field("types", &self.calculators.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for CalculatorRegistry {
+    fn default() -> Self {
+        let builtin: Arc<dyn Calculator> = Arc::new(BuiltinCalculator);
+        Self {
+            calculators: info::BUILTIN_TYPES
+                .iter()
+                .map(|ext| (ResourceType::from(*ext), builtin.clone()))
+                .collect(),
+            fallback:    builtin,
+        }
+    }
+}
+
+impl CalculatorRegistry {
+    /// Creates a registry prefilled with the built-in calculators.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces the calculator for a resource type, returning the one
+    /// it replaced.
+    pub fn register<T: Into<ResourceType>, C: Calculator + 'static>(
+        &mut self,
+        resource_type: T,
+        calculator: C,
+    ) -> Option<Arc<dyn Calculator>> {
+        self.calculators
+            .insert(resource_type.into(), Arc::new(calculator))
+    }
+
+    /// Removes the calculator for a resource type, so the fallback is used.
+    pub fn unregister(&mut self, resource_type: &ResourceType) -> Option<Arc<dyn Calculator>> {
+        self.calculators.remove(resource_type)
+    }
+
+    /// Replaces the calculator used for types without one of their own.
+    pub fn set_fallback<C: Calculator + 'static>(&mut self, calculator: C) {
+        self.fallback = Arc::new(calculator);
+    }
+
+    /// Checks whether a resource type has a calculator of its own.
+    pub fn contains(&self, resource_type: &ResourceType) -> bool {
+        self.calculators.contains_key(resource_type)
+    }
+
+    /// Iterates the resource types with a calculator of their own.
+    pub fn types(&self) -> impl Iterator<Item = &ResourceType> {
+        self.calculators.keys()
+    }
+
+    /// Gets the calculator used for a resource type.
+    pub fn get(&self, resource_type: &ResourceType) -> &dyn Calculator {
+        self.calculators
+            .get(resource_type)
+            .unwrap_or(&self.fallback)
+            .as_ref()
+    }
+
+    fn get_for_name(&self, name: &str) -> Option<&dyn Calculator> {
+        Some(self.get(&ResourceType::from_name(name)?))
+    }
+
+    /// Infallibly calculate an RSTB value from a byte slice and filename,
+    /// returning `None` if the type is not supported.
+    pub fn calc_from_bytes_and_name(
+        &self,
+        bytes: &[u8],
+        name: &str,
+        platform: Platform,
+    ) -> Option<u32> {
+        if bytes.len() < 8 {
+            return None;
+        }
+        self.get_for_name(name)?.calc(bytes, name, platform)
+    }
+
+    /// Infallibly calculate *or* estimate an RSTB value from a byte slice and
+    /// filename, returning `None` if the type is not supported.
+    pub fn estimate_from_bytes_and_name(
+        &self,
+        bytes: &[u8],
+        name: &str,
+        platform: Platform,
+    ) -> Option<u32> {
+        if bytes.len() < 8 {
+            return None;
+        }
+        self.get_for_name(name)?.estimate(bytes, name, platform)
+    }
+
+    /// Infallibly calculate an RSTB value from an uncompressed file size and
+    /// filename, returning `None` if the type is not supported.
+    pub fn calc_from_size_and_name(
+        &self,
+        filesize: usize,
+        name: &str,
+        platform: Platform,
+    ) -> Option<u32> {
+        self.get_for_name(name)?.calc_size(filesize, name, platform)
+    }
+
+    /// Infallibly calculate *or* estimate an RSTB value from an uncompressed
+    /// file size and filename, returning `None` if the type is not supported.
+    pub fn estimate_from_size_and_name(
+        &self,
+        filesize: usize,
+        name: &str,
+        platform: Platform,
+    ) -> Option<u32> {
+        self.get_for_name(name)?
+            .estimate_size(filesize, name, platform)
+    }
+}
+
+fn default_registry() -> &'static RwLock<CalculatorRegistry> {
+    static REGISTRY: OnceLock<RwLock<CalculatorRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Runs a closure with the default registry, which the free calculation
+/// functions use.
+pub fn with_default_registry<R>(f: impl FnOnce(&CalculatorRegistry) -> R) -> R {
+    f(&default_registry()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()))
+}
+
+/// Adds or replaces the calculator for a resource type in the default
+/// registry, returning the one it replaced.
+pub fn register_calculator<T: Into<ResourceType>, C: Calculator + 'static>(
+    resource_type: T,
+    calculator: C,
+) -> Option<Arc<dyn Calculator>> {
+    default_registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .register(resource_type, calculator)
+}
+
+/// Replaces the default registry, e.g. with [`CalculatorRegistry::new()`] to
+/// undo all registrations.
+pub fn set_default_registry(registry: CalculatorRegistry) {
+    *default_registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = registry;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(u32);
+
+    impl Calculator for Fixed {
+        fn calc_size(&self, _filesize: usize, _name: &str, _platform: Platform) -> Option<u32> {
+            Some(self.0)
+        }
+    }
+
+    #[test]
+    fn override_type() {
+        let mut registry = CalculatorRegistry::new();
+        let name = "Actor/Pack/Test.sbactorpack";
+        let builtin = registry.calc_from_size_and_name(1000, name, Platform::WiiU);
+        assert_eq!(builtin, Some(1024 + 0xe4 + 0x3c));
+        assert!(registry.register("bactorpack", Fixed(5)).is_some());
+        assert_eq!(
+            registry.calc_from_size_and_name(1000, name, Platform::WiiU),
+            Some(5)
+        );
+        // Estimates fall back to the calculation
+        assert_eq!(
+            registry.estimate_from_size_and_name(1000, name, Platform::WiiU),
+            Some(5)
+        );
+        registry.unregister(&ResourceType::from("bactorpack"));
+        assert_eq!(
+            registry.calc_from_size_and_name(1000, name, Platform::WiiU),
+            builtin
+        );
+        registry.set_fallback(Fixed(7));
+        assert_eq!(
+            registry.calc_from_size_and_name(1000, name, Platform::WiiU),
+            Some(7)
+        );
+    }
+}
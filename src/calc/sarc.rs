@@ -0,0 +1,74 @@
+//! A minimal SARC reader, listing the named files in an archive so nested
+//! resources can be walked without the `complex` feature.
+
+/// Returns the name and data of every named file in decompressed SARC data,
+/// or `None` if the archive cannot be read.
+pub(crate) fn files(bytes: &[u8]) -> Option<Vec<(String, &[u8])>> {
+    if bytes.get(0..4)? != b"SARC" {
+        return None;
+    }
+    let big_endian = match bytes.get(0x6..0x8)? {
+        [0xfe, 0xff] => true,
+        [0xff, 0xfe] => false,
+        _ => return None,
+    };
+    let u16_at = |offset: usize| -> Option<usize> {
+        let raw: [u8; 2] = bytes.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if big_endian {
+            u16::from_be_bytes(raw)
+        } else {
+            u16::from_le_bytes(raw)
+        } as usize)
+    };
+    let u32_at = |offset: usize| -> Option<usize> {
+        let raw: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(raw)
+        } else {
+            u32::from_le_bytes(raw)
+        } as usize)
+    };
+    let data_offset = u32_at(0xc)?;
+    let sfat = u16_at(0x4)?;
+    if bytes.get(sfat..sfat + 4)? != b"SFAT" {
+        return None;
+    }
+    let node_count = u16_at(sfat + 0x6)?;
+    let nodes = sfat + u16_at(sfat + 0x4)?;
+    let sfnt = nodes + node_count * 0x10;
+    if bytes.get(sfnt..sfnt + 4)? != b"SFNT" {
+        return None;
+    }
+    let names = sfnt + u16_at(sfnt + 0x4)?;
+    let mut files = Vec::with_capacity(node_count);
+    for node in (nodes..sfnt).step_by(0x10) {
+        let attributes = u32_at(node + 0x4)?;
+        // Files without the name flag can only be found by hash
+        if attributes >> 24 != 1 {
+            continue;
+        }
+        let name_start = names + (attributes & 0xffff) * 4;
+        let name_len = bytes.get(name_start..)?.iter().position(|b| *b == 0)?;
+        let name = std::str::from_utf8(&bytes[name_start..name_start + name_len]).ok()?;
+        let start = data_offset + u32_at(node + 0x8)?;
+        let end = data_offset + u32_at(node + 0xc)?;
+        files.push((name.to_owned(), bytes.get(start..end)?));
+    }
+    Some(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calc::decompress;
+
+    #[test]
+    fn list_files() {
+        let bytes = std::fs::read("test/AirOcta_Tag.sbactorpack").unwrap();
+        let bytes = decompress::yaz0(&bytes).unwrap();
+        let files = super::files(&bytes).unwrap();
+        assert!(files
+            .iter()
+            .any(|(name, _)| name == "Actor/ActorLink/AirOcta_Tag.bxml"));
+        assert!(files.iter().all(|(_, data)| !data.is_empty()));
+    }
+}
@@ -44,7 +44,9 @@
 //! - **`botw-data`**: Enables access to actual RSTB data from BOTW. This will enable filename
 //!   serialization in the [`to_text()`](ResourceSizeTable::to_text()) method if `json` is enabled,
 //!   and more importantly enables the [`new_from_stock()`](ResourceSizeTable::new_from_stock)
-//!   method to create a copy of the original BOTW RSTB (1.5.0 Wii U or 1.6.0 Switch).
+//!   method to create a copy of the original BOTW RSTB (1.5.0 Wii U or 1.6.0 Switch), which
+//!   [`port_from_stock()`](ResourceSizeTable::port_from_stock) uses to port an RSTB between
+//!   platforms.
 #![cfg_attr(not(test), deny(clippy::unwrap_used))]
 
 mod bin;
@@ -53,7 +55,9 @@ mod botw;
 pub mod calc;
 #[cfg(feature = "json")]
 mod json;
+mod port;
 mod str;
+mod update;
 
 use std::{borrow::Borrow, collections::BTreeMap};
 
@@ -61,21 +65,86 @@ use std::{borrow::Borrow, collections::BTreeMap};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-pub use crate::str::FixedString;
+#[cfg(feature = "botw-data")]
+pub use crate::botw::canonical_name;
+pub use crate::{
+    port::{PortMethod, PortReport, PortedEntry},
+    str::FixedString,
+    update::{OnFailure, UpdateAction, UpdatePolicy},
+};
 
 pub type Result<T> = std::result::Result<T, RstbError>;
 const CRC32: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
 
-/// An enum representing RSTB endianness. Note that it also serves as shorthand
-/// for Wii U (`Big`) or Switch (`Little`) more broadly. So if you are
-/// performing an RSTB value calculation or generating a stock RSTB, always use
-/// `Endian::Big` for Wii U and `Endian::Little` for Switch.
+/// An enum representing RSTB byte order. For anything that depends on the
+/// platform more broadly, such as calculations or the stock RSTB, use
+/// [`Platform`]; an `Endian` converts into the platform whose RSTB uses that
+/// byte order (`Big` for Wii U, `Little` for Switch).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Endian {
     Big,
     Little,
 }
 
+impl From<Platform> for Endian {
+    fn from(platform: Platform) -> Self {
+        platform.endian()
+    }
+}
+
+/// The platform, and with it the game version, that an RSTB or resource
+/// belongs to. It determines the pointer size of the game's classes, the
+/// constants used in RSTB calculations, and which stock RSTB applies (1.5.0
+/// for Wii U, 1.6.0 for Switch).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Platform {
+    WiiU,
+    Switch,
+}
+
+impl Platform {
+    /// The byte order of this platform's RSTB.
+    pub const fn endian(self) -> Endian {
+        match self {
+            Self::WiiU => Endian::Big,
+            Self::Switch => Endian::Little,
+        }
+    }
+
+    /// The size in bytes of a pointer in the game's classes.
+    pub const fn pointer_size(self) -> u32 {
+        match self {
+            Self::WiiU => 4,
+            Self::Switch => 8,
+        }
+    }
+
+    /// The constant added to the value of every resource the game parses.
+    pub const fn parse_const(self) -> u32 {
+        match self {
+            Self::WiiU => 0xe4,
+            Self::Switch => 0x168,
+        }
+    }
+
+    /// The game version of this platform's stock RSTB.
+    pub const fn game_version(self) -> &'static str {
+        match self {
+            Self::WiiU => "1.5.0",
+            Self::Switch => "1.6.0",
+        }
+    }
+}
+
+impl From<Endian> for Platform {
+    fn from(endian: Endian) -> Self {
+        match endian {
+            Endian::Big => Self::WiiU,
+            Endian::Little => Self::Switch,
+        }
+    }
+}
+
 /// An enum representing the possible keys into an RSTB, whether as CRC hashes
 /// or resource names.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -182,35 +251,33 @@ impl ResourceSizeTable {
 
     /// Sets the RSTB value for the specified resource name by calculating it
     /// from its data as a byte slice. If the new value cannot be determined,
-    /// checks for an existing entry and removes it.
-    pub fn set_from_slice<S: AsRef<str>, B: AsRef<[u8]>>(
+    /// checks for an existing entry and removes it. See
+    /// [`set_from_slice_with_policy()`](Self::set_from_slice_with_policy) for
+    /// other ways to handle entries.
+    pub fn set_from_slice<S: AsRef<str>, B: AsRef<[u8]>, P: Into<Platform>>(
         &mut self,
         entry: S,
         data: B,
-        endian: Endian,
+        platform: P,
     ) {
-        let name = entry.as_ref();
-        match calc::calc_from_slice_and_name(data.as_ref(), name, endian) {
-            Some(value) => self.crc_map.insert(CRC32.checksum(name.as_bytes()), value),
-            None => self.remove(name),
-        };
+        self.set_from_slice_with_policy(entry, data, platform, &UpdatePolicy::default());
     }
 
     /// Sets the RSTB value for the specified resource name by calculating it
     /// from its data as a byte slice. If the new value cannot be determined,
     /// checks for an existing entry and removes it. Applies estimated values
     /// for supported AAMP and BFRES files if applicable.
-    pub fn set_from_slice_with_estimate<S: AsRef<str>, B: AsRef<[u8]>>(
+    pub fn set_from_slice_with_estimate<S: AsRef<str>, B: AsRef<[u8]>, P: Into<Platform>>(
         &mut self,
         entry: S,
         data: B,
-        endian: Endian,
+        platform: P,
     ) {
-        let name = entry.as_ref();
-        match calc::estimate_from_slice_and_name(data.as_ref(), name, endian) {
-            Some(value) => self.crc_map.insert(CRC32.checksum(name.as_bytes()), value),
-            None => self.remove(name),
+        let policy = UpdatePolicy {
+            estimate: true,
+            ..Default::default()
         };
+        self.set_from_slice_with_policy(entry, data, platform, &policy);
     }
 
     /// Sets the RSTB value for the specified hash or resource name in the name table.
@@ -229,7 +296,7 @@ impl ResourceSizeTable {
     }
 
     /// Gets an interator over all RSTB entries across both the CRC and name tables.
-    pub fn iter(&self) -> impl Iterator<Item = (ResourceKey, &u32)> {
+    pub fn iter(&self) -> impl Iterator<Item = (ResourceKey<'_>, &u32)> {
         self.crc_map
             .iter()
             .map(|(k, v)| (ResourceKey::Hash(*k), v))
@@ -241,7 +308,7 @@ impl ResourceSizeTable {
     }
 
     /// Gets a mutable interator over all RSTB entries across both the CRC and name tables.
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = (ResourceKey, &mut u32)> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (ResourceKey<'_>, &mut u32)> {
         self.crc_map
             .iter_mut()
             .map(|(k, v)| (ResourceKey::Hash(*k), v))
@@ -0,0 +1,481 @@
+//! `rstb`, a command-line tool for inspecting and editing BOTW resource size
+//! tables. Run `rstb --help` for usage.
+
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use rstb::{calc, Endian, Platform, ResourceKey, ResourceSizeTable, UpdatePolicy};
+
+const USAGE: &str = "\
+Usage: rstb <command> [options]
+
+Commands:
+  get <rstb> <entry>...          Print the values of entries
+  set <rstb> <entry> <value>     Set the value of an entry
+  set <rstb> <name> --file <f>   Set the value of an entry calculated from a file
+  remove <rstb> <entry>...       Remove entries
+  list <rstb>                    List every entry and its value
+  to-json <rstb>                 Convert an RSTB to JSON
+  from-json <json> -o <out>      Convert JSON to an RSTB
+  convert <rstb>                 Rewrite an RSTB, porting it with --platform
+  calc <file>...                 Calculate the values of resource files
+
+An entry is a resource name (e.g. Actor/Pack/Enemy_Lizalfos_Dark.bactorpack)
+or a CRC32 hash of one, in decimal or with a 0x prefix.
+
+Options:
+  -o, --output <path>     Write to this path instead of modifying the input
+  -p, --platform <name>   wiiu (or big) or switch (or little). Defaults to
+                          the input's byte order, or for calc to the
+                          platform detected from each file. set and remove
+                          only accept the input's platform; use convert to
+                          change it
+  -f, --file <path>       Resource file to calculate the value for with set
+  -e, --estimate          Estimate values that cannot be calculated exactly
+      --compress          Yaz0 compress the output
+      --no-compress       Do not compress the output
+      --pretty            Pretty-print JSON output
+  -h, --help              Show this message
+
+Converting to another platform ports the values with the stock tables
+(requires the botw-data feature): unchanged entries take the target's stock
+value and the rest are scaled by their stock ratios, as estimates.
+
+Output is compressed if the input was, unless the output path ends with
+.srsizetable (compressed) or .rsizetable (uncompressed) or a compression
+option is given.";
+
+type CliResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    positional: Vec<String>,
+    output: Option<PathBuf>,
+    platform: Option<Platform>,
+    file: Option<PathBuf>,
+    estimate: bool,
+    compress: Option<bool>,
+    pretty: bool,
+    help: bool,
+}
+
+impl Args {
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> CliResult<Self> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{arg} requires a value"));
+            match arg.as_str() {
+                "-o" | "--output" => parsed.output = Some(value()?.into()),
+                "-p" | "--platform" => parsed.platform = Some(parse_platform(&value()?)?),
+                "-f" | "--file" => parsed.file = Some(value()?.into()),
+                "-e" | "--estimate" => parsed.estimate = true,
+                "--compress" => parsed.compress = Some(true),
+                "--no-compress" => parsed.compress = Some(false),
+                "--pretty" => parsed.pretty = true,
+                "-h" | "--help" => parsed.help = true,
+                "--" => {
+                    parsed.positional.extend(args);
+                    break;
+                }
+                flag if flag.starts_with('-') && flag.len() > 1 => {
+                    return Err(format!("unknown option {flag}").into());
+                }
+                _ => parsed.positional.push(arg),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Gets the positional argument at the index, naming it in the error if
+    /// it is missing.
+    fn arg(&self, index: usize, name: &str) -> CliResult<&str> {
+        self.positional
+            .get(index)
+            .map(String::as_str)
+            .ok_or_else(|| format!("missing argument <{name}>").into())
+    }
+}
+
+fn parse_platform(name: &str) -> CliResult<Platform> {
+    match name.to_ascii_lowercase().as_str() {
+        "wiiu" | "wii-u" | "big" | "be" => Ok(Platform::WiiU),
+        "switch" | "nx" | "little" | "le" => Ok(Platform::Switch),
+        _ => Err(format!("unknown platform {name}, expected wiiu or switch").into()),
+    }
+}
+
+fn parse_number(value: &str) -> CliResult<u32> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.map_err(|_| format!("invalid number {value}").into())
+}
+
+/// Treats an entry as a hash if it is a number, or as a name otherwise.
+fn parse_key(entry: &str) -> ResourceKey<'_> {
+    match parse_number(entry) {
+        Ok(hash) => ResourceKey::Hash(hash),
+        Err(_) => ResourceKey::Name(entry),
+    }
+}
+
+fn display_key(key: ResourceKey<'_>) -> String {
+    match key {
+        ResourceKey::Name(name) => name.to_owned(),
+        ResourceKey::Hash(hash) => {
+            #[cfg(feature = "botw-data")]
+            if let Some(name) = rstb::canonical_name(hash) {
+                return name.to_owned();
+            }
+            format!("0x{hash:08x}")
+        }
+    }
+}
+
+/// An RSTB read from disk, with what is needed to write it back the same way.
+struct Input {
+    path: PathBuf,
+    table: ResourceSizeTable,
+    endian: Endian,
+    compressed: bool,
+}
+
+impl Input {
+    fn read(path: &str) -> CliResult<Self> {
+        let bytes = std::fs::read(path).map_err(|e| format!("{path}: {e}"))?;
+        let compressed = bytes.starts_with(b"Yaz0");
+        let (table, endian) = ResourceSizeTable::from_binary_with_endian(bytes)
+            .map_err(|e| format!("{path}: {e}"))?;
+        Ok(Self {
+            path: path.into(),
+            table,
+            endian,
+            compressed,
+        })
+    }
+
+    /// Writes the table to the output path or back over the input, in the
+    /// input's byte order.
+    fn write(&self, args: &Args) -> CliResult<()> {
+        let path = args.output.as_deref().unwrap_or(&self.path);
+        write_table(
+            &self.table,
+            path,
+            self.endian,
+            args.compress,
+            self.compressed,
+        )
+    }
+
+    /// Rejects a requested platform other than the input's, since editing an
+    /// entry can't port the rest of the table to it.
+    fn check_platform(&self, args: &Args) -> CliResult<()> {
+        match args.platform {
+            Some(platform) if platform != Platform::from(self.endian) => {
+                Err(format!(
+                    "{} is a {:?} RSTB, use convert to port it to {platform:?}",
+                    self.path.display(),
+                    Platform::from(self.endian)
+                )
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn write_table(
+    table: &ResourceSizeTable,
+    path: &Path,
+    endian: Endian,
+    compress: Option<bool>,
+    default_compress: bool,
+) -> CliResult<()> {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let compress = compress.unwrap_or(if name.ends_with(".srsizetable") {
+        true
+    } else if name.ends_with(".rsizetable") {
+        false
+    } else {
+        default_compress
+    });
+    let bytes = if compress {
+        #[cfg(feature = "yaz0")]
+        {
+            table.to_compressed_binary(endian)
+        }
+        #[cfg(not(feature = "yaz0"))]
+        return Err(rstb::RstbError::FeatureError("yaz0".to_owned()).into());
+    } else {
+        table.to_binary(endian)
+    };
+    std::fs::write(path, bytes).map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(())
+}
+
+fn get(args: &Args) -> CliResult<()> {
+    let input = Input::read(args.arg(1, "rstb")?)?;
+    args.arg(2, "entry")?;
+    let mut missing = false;
+    for entry in &args.positional[2..] {
+        match input.table.get(parse_key(entry)) {
+            Some(value) => println!("{value}"),
+            None => {
+                eprintln!("{entry} is not in the RSTB");
+                missing = true;
+            }
+        }
+    }
+    if missing {
+        Err("some entries were not found".into())
+    } else {
+        Ok(())
+    }
+}
+
+fn set(args: &Args) -> CliResult<()> {
+    let mut input = Input::read(args.arg(1, "rstb")?)?;
+    input.check_platform(args)?;
+    let entry = args.arg(2, "entry")?;
+    match &args.file {
+        Some(file) => {
+            let bytes = std::fs::read(file).map_err(|e| format!("{}: {e}", file.display()))?;
+            let platform = Platform::from(input.endian);
+            let policy = UpdatePolicy {
+                estimate: args.estimate,
+                ..Default::default()
+            };
+            let action = input
+                .table
+                .set_from_slice_with_policy(entry, bytes, platform, &policy);
+            println!("{entry}: {action:?}");
+        }
+        None => {
+            let value = parse_number(args.arg(3, "value")?)?;
+            input.table.set(parse_key(entry), value);
+        }
+    }
+    input.write(args)
+}
+
+fn remove(args: &Args) -> CliResult<()> {
+    let mut input = Input::read(args.arg(1, "rstb")?)?;
+    input.check_platform(args)?;
+    args.arg(2, "entry")?;
+    for entry in &args.positional[2..] {
+        if input.table.remove(parse_key(entry)).is_none() {
+            eprintln!("{entry} is not in the RSTB");
+        }
+    }
+    input.write(args)
+}
+
+fn list(args: &Args) -> CliResult<()> {
+    let input = Input::read(args.arg(1, "rstb")?)?;
+    let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+    for (key, value) in input.table.iter() {
+        writeln!(stdout, "{}\t{value}", display_key(key))?;
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
+fn to_json(args: &Args) -> CliResult<()> {
+    let input = Input::read(args.arg(1, "rstb")?)?;
+    let text = if args.pretty {
+        input.table.to_text_pretty()
+    } else {
+        input.table.to_text()
+    };
+    match &args.output {
+        Some(path) => std::fs::write(path, text).map_err(|e| format!("{}: {e}", path.display()))?,
+        None => writeln!(std::io::stdout().lock(), "{text}")?,
+    }
+    Ok(())
+}
+
+fn from_json(args: &Args) -> CliResult<()> {
+    let path = args.arg(1, "json")?;
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let table = ResourceSizeTable::from_text(text).map_err(|e| format!("{path}: {e}"))?;
+    let output = args
+        .output
+        .as_deref()
+        .ok_or("from-json requires an --output path")?;
+    let platform = args
+        .platform
+        .ok_or("from-json requires a --platform for the byte order")?;
+    write_table(&table, output, platform.into(), args.compress, false)
+}
+
+fn convert(args: &Args) -> CliResult<()> {
+    let input = Input::read(args.arg(1, "rstb")?)?;
+    match args.platform {
+        Some(to) if to != Platform::from(input.endian) => {
+            Input {
+                table: port(&input.table, to)?,
+                endian: to.into(),
+                ..input
+            }
+            .write(args)
+        }
+        _ => input.write(args),
+    }
+}
+
+/// Ports a table's values to another platform using the stock tables.
+#[cfg(feature = "botw-data")]
+fn port(table: &ResourceSizeTable, to: Platform) -> CliResult<ResourceSizeTable> {
+    let report = table.port_from_stock(to, std::iter::empty::<(&str, &[u8])>());
+    eprintln!(
+        "{} of {} entries were extrapolated",
+        report.extrapolated().count(),
+        report.entries.len()
+    );
+    Ok(report.table)
+}
+
+#[cfg(not(feature = "botw-data"))]
+fn port(_: &ResourceSizeTable, _: Platform) -> CliResult<ResourceSizeTable> {
+    Err(rstb::RstbError::FeatureError("botw-data".to_owned()).into())
+}
+
+fn calc(args: &Args) -> CliResult<()> {
+    args.arg(1, "file")?;
+    let mut failed = false;
+    for file in &args.positional[1..] {
+        let path = Path::new(file);
+        let bytes = std::fs::read(path).map_err(|e| format!("{file}: {e}"))?;
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let platform = match args.platform {
+            Some(platform) => platform,
+            None => {
+                match calc::detect_platform(&bytes) {
+                    Some(detection) => detection.platform,
+                    None => {
+                        eprintln!("{file}: cannot detect the platform, use --platform");
+                        failed = true;
+                        continue;
+                    }
+                }
+            }
+        };
+        let value = if args.estimate {
+            calc::estimate_from_bytes_and_name(&bytes, name, platform)
+        } else {
+            calc::calc_from_slice_and_name(&bytes, name, platform)
+        };
+        match value {
+            Some(value) => println!("{file}\t{value}"),
+            None => {
+                eprintln!("{file}: unsupported type");
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        Err("some files could not be calculated".into())
+    } else {
+        Ok(())
+    }
+}
+
+fn run(args: Args) -> CliResult<()> {
+    let command = match args.positional.first() {
+        Some(command) if !args.help => command.as_str(),
+        _ => {
+            println!("{USAGE}");
+            return Ok(());
+        }
+    };
+    match command {
+        "get" => get(&args),
+        "set" => set(&args),
+        "remove" => remove(&args),
+        "list" => list(&args),
+        "to-json" => to_json(&args),
+        "from-json" => from_json(&args),
+        "convert" => convert(&args),
+        "calc" => calc(&args),
+        _ => Err(format!("unknown command {command}, see rstb --help").into()),
+    }
+}
+
+fn main() -> ExitCode {
+    match Args::parse(std::env::args().skip(1)).and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        // Output piped into e.g. `head` can be closed early
+        Err(e)
+            if e.downcast_ref::<std::io::Error>()
+                .is_some_and(|e| e.kind() == std::io::ErrorKind::BrokenPipe) =>
+        {
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_args() {
+        let args = Args::parse(
+            [
+                "convert",
+                "in.srsizetable",
+                "-p",
+                "switch",
+                "--no-compress",
+                "-o",
+                "out",
+            ]
+            .map(String::from),
+        )
+        .unwrap();
+        assert_eq!(args.positional, ["convert", "in.srsizetable"]);
+        assert_eq!(args.platform, Some(Platform::Switch));
+        assert_eq!(args.compress, Some(false));
+        assert_eq!(args.output, Some("out".into()));
+        assert!(Args::parse(["--output".to_owned()]).is_err());
+        assert!(Args::parse(["--bogus".to_owned()]).is_err());
+        assert_eq!(parse_key("0x10"), ResourceKey::Hash(16));
+        assert_eq!(parse_key("16"), ResourceKey::Hash(16));
+        assert_eq!(
+            parse_key("Actor/Pack/Test.bactorpack"),
+            ResourceKey::Name("Actor/Pack/Test.bactorpack")
+        );
+    }
+
+    #[test]
+    fn edits_keep_platform() {
+        let input = Input {
+            path: "in.rsizetable".into(),
+            table: ResourceSizeTable::default(),
+            endian: Endian::Big,
+            compressed: false,
+        };
+        let args = |platform| Args {
+            platform,
+            ..Default::default()
+        };
+        assert!(input.check_platform(&args(None)).is_ok());
+        assert!(input.check_platform(&args(Some(Platform::WiiU))).is_ok());
+        assert!(input.check_platform(&args(Some(Platform::Switch))).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+
+use crate::{calc, FixedString, Platform, ResourceSizeTable, CRC32};
+
+/// How the value of a ported RSTB entry was determined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortMethod {
+    /// Calculated from the resource's bytes.
+    Calculated,
+    /// Estimated from the resource's bytes, for a type without an infallible
+    /// calculation.
+    Estimated,
+    /// The entry was unmodified, so the target platform's stock value was
+    /// used.
+    Stock,
+    /// Scaled by the ratio between the entry's stock values on the two
+    /// platforms.
+    StockRatio,
+    /// Not in both stock tables, so scaled by the ratio between the stock
+    /// tables as a whole.
+    TableRatio,
+}
+
+impl PortMethod {
+    /// Returns true if the value is exact rather than estimated or
+    /// extrapolated.
+    pub fn is_exact(self) -> bool {
+        matches!(self, Self::Calculated | Self::Stock)
+    }
+}
+
+/// A single entry of a ported RSTB.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortedEntry {
+    pub hash:   u32,
+    /// The resource name, if known from the name table or the resources
+    /// passed in.
+    pub name:   Option<String>,
+    pub value:  u32,
+    pub method: PortMethod,
+}
+
+/// The result of porting an RSTB to another platform.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PortReport {
+    pub table:   ResourceSizeTable,
+    pub entries: Vec<PortedEntry>,
+}
+
+impl PortReport {
+    /// Iterates the entries whose values are not exact.
+    pub fn extrapolated(&self) -> impl Iterator<Item = &PortedEntry> {
+        self.entries.iter().filter(|entry| !entry.method.is_exact())
+    }
+}
+
+impl ResourceSizeTable {
+    /// Ports this RSTB to another platform, given the stock RSTBs of its own
+    /// platform and the target platform. The result starts from the target's
+    /// stock table, with this table's removals, changes and additions
+    /// applied; entries it still holds at their stock value that only exist
+    /// on its own platform are dropped. Entries are recalculated for the
+    /// target platform from any resources passed in, which are keyed by
+    /// their RSTB name (e.g. `Actor/Pack/Enemy_Lizalfos_Dark.bactorpack`) and
+    /// may be Yaz0 compressed. Any other changed or added entries are scaled
+    /// by the ratio between their stock values on each platform, or between
+    /// the stock tables as a whole for resources not in both.
+    pub fn port<I, S, B>(
+        &self,
+        from_stock: &ResourceSizeTable,
+        to_stock: &ResourceSizeTable,
+        to: impl Into<Platform>,
+        resources: I,
+    ) -> PortReport
+    where
+        I: IntoIterator<Item = (S, B)>,
+        S: AsRef<str>,
+        B: AsRef<[u8]>,
+    {
+        let to = to.into();
+        let recalculated: HashMap<u32, (String, u32, PortMethod)> = resources
+            .into_iter()
+            .filter_map(|(name, bytes)| {
+                let (name, bytes) = (name.as_ref(), bytes.as_ref());
+                let (value, method) = calc::calc_from_slice_and_name(bytes, name, to)
+                    .map(|value| (value, PortMethod::Calculated))
+                    .or_else(|| {
+                        calc::estimate_from_slice_and_name(bytes, name, to)
+                            .map(|value| (value, PortMethod::Estimated))
+                    })?;
+                Some((
+                    CRC32.checksum(name.as_bytes()),
+                    (name.to_owned(), value, method),
+                ))
+            })
+            .collect();
+        let table_ratio = table_ratio(from_stock, to_stock);
+        let mut table = to_stock.clone();
+        let mut methods: HashMap<u32, PortMethod> = HashMap::new();
+
+        for hash in from_stock.crc_map.keys() {
+            if !self.crc_map.contains_key(hash) {
+                table.crc_map.remove(hash);
+            }
+        }
+        for (&hash, &value) in &self.crc_map {
+            let from = from_stock.crc_map.get(&hash);
+            let to = to_stock.crc_map.get(&hash);
+            if to.is_none() && from == Some(&value) {
+                continue;
+            }
+            let (value, method) = extrapolate(value, from, to, table_ratio);
+            table.crc_map.insert(hash, value);
+            methods.insert(hash, method);
+        }
+        for name in from_stock.name_map.keys() {
+            if !self.name_map.contains_key(name) {
+                table.name_map.remove(name);
+            }
+        }
+        for (name, &value) in &self.name_map {
+            let from = from_stock.name_map.get(name);
+            let to = to_stock.name_map.get(name);
+            if to.is_none() && from == Some(&value) {
+                continue;
+            }
+            let (value, method) = extrapolate(value, from, to, table_ratio);
+            table.name_map.insert(*name, value);
+            methods.insert(CRC32.checksum(name.as_str().as_bytes()), method);
+        }
+        for (&hash, (name, value, method)) in &recalculated {
+            match table.name_map.get_mut(&FixedString::new(name.as_str())) {
+                Some(entry) => *entry = *value,
+                None => {
+                    table.crc_map.insert(hash, *value);
+                }
+            }
+            methods.insert(hash, *method);
+        }
+
+        let mut entries: Vec<PortedEntry> = table
+            .crc_map
+            .iter()
+            .map(|(&hash, &value)| {
+                PortedEntry {
+                    hash,
+                    name: recalculated.get(&hash).map(|(name, ..)| name.clone()),
+                    value,
+                    method: methods.get(&hash).copied().unwrap_or(PortMethod::Stock),
+                }
+            })
+            .collect();
+        entries.extend(table.name_map.iter().map(|(name, &value)| {
+            let hash = CRC32.checksum(name.as_str().as_bytes());
+            PortedEntry {
+                hash,
+                name: Some(name.as_str().to_owned()),
+                value,
+                method: methods.get(&hash).copied().unwrap_or(PortMethod::Stock),
+            }
+        }));
+        PortReport { table, entries }
+    }
+
+    /// *Requires the `botw-data` feature.*
+    /// Ports this RSTB to another platform as with [`port()`](Self::port),
+    /// using the stock RSTBs included with the crate.
+    #[cfg(feature = "botw-data")]
+    pub fn port_from_stock<I, S, B>(&self, to: impl Into<Platform>, resources: I) -> PortReport
+    where
+        I: IntoIterator<Item = (S, B)>,
+        S: AsRef<str>,
+        B: AsRef<[u8]>,
+    {
+        let to = to.into();
+        let from = match to {
+            Platform::WiiU => Platform::Switch,
+            Platform::Switch => Platform::WiiU,
+        };
+        self.port(
+            &Self::new_from_stock(from),
+            &Self::new_from_stock(to),
+            to,
+            resources,
+        )
+    }
+}
+
+/// The ratio between the total values of the entries in both stock tables.
+fn table_ratio(from_stock: &ResourceSizeTable, to_stock: &ResourceSizeTable) -> f64 {
+    let (from_total, to_total) = from_stock
+        .crc_map
+        .iter()
+        .filter_map(|(hash, from)| Some((*from as u64, *to_stock.crc_map.get(hash)? as u64)))
+        .fold((0, 0), |(from_total, to_total), (from, to)| {
+            (from_total + from, to_total + to)
+        });
+    if from_total == 0 {
+        1.0
+    } else {
+        to_total as f64 / from_total as f64
+    }
+}
+
+fn extrapolate(
+    value: u32,
+    from_stock: Option<&u32>,
+    to_stock: Option<&u32>,
+    table_ratio: f64,
+) -> (u32, PortMethod) {
+    let scale = |ratio: f64| (value as f64 * ratio).ceil() as u32;
+    match (from_stock, to_stock) {
+        (Some(&from), Some(&to)) if from == value => (to, PortMethod::Stock),
+        (Some(&from), Some(&to)) if from > 0 => {
+            (scale(to as f64 / from as f64), PortMethod::StockRatio)
+        }
+        _ => (scale(table_ratio), PortMethod::TableRatio),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn port_entries() {
+        let mut from_stock = ResourceSizeTable::default();
+        let mut to_stock = ResourceSizeTable::default();
+        from_stock.set("Actor/Pack/A.bactorpack", 1000);
+        to_stock.set("Actor/Pack/A.bactorpack", 1500);
+        from_stock.set("Actor/Pack/B.bactorpack", 3000);
+        to_stock.set("Actor/Pack/B.bactorpack", 3500);
+        let mut table = from_stock.clone();
+        table.set("Actor/Pack/B.bactorpack", 2000);
+        table.set("Actor/Pack/C.bactorpack", 400);
+        table.set("Map/MainField/A-1/A-1_Dynamic.mubin", 1);
+        let bytes = std::fs::read("test/A-1_Dynamic.smubin").unwrap();
+        let report = table.port(&from_stock, &to_stock, Platform::Switch, [(
+            "Map/MainField/A-1/A-1_Dynamic.mubin",
+            bytes,
+        )]);
+        assert_eq!(report.table.get("Actor/Pack/A.bactorpack"), Some(1500));
+        assert_eq!(report.table.get("Actor/Pack/B.bactorpack"), Some(2334));
+        assert_eq!(report.table.get("Actor/Pack/C.bactorpack"), Some(500));
+        assert_eq!(
+            report.table.get("Map/MainField/A-1/A-1_Dynamic.mubin"),
+            calc::calc_from_file("test/A-1_Dynamic.smubin", Platform::Switch).unwrap()
+        );
+        let methods: HashMap<u32, PortMethod> = report
+            .entries
+            .iter()
+            .map(|entry| (entry.hash, entry.method))
+            .collect();
+        let method = |name: &str| methods[&CRC32.checksum(name.as_bytes())];
+        assert_eq!(method("Actor/Pack/A.bactorpack"), PortMethod::Stock);
+        assert_eq!(method("Actor/Pack/B.bactorpack"), PortMethod::StockRatio);
+        assert_eq!(method("Actor/Pack/C.bactorpack"), PortMethod::TableRatio);
+        assert_eq!(
+            method("Map/MainField/A-1/A-1_Dynamic.mubin"),
+            PortMethod::Calculated
+        );
+        assert_eq!(report.extrapolated().count(), 2);
+    }
+
+    #[test]
+    fn port_removals() {
+        let mut from_stock = ResourceSizeTable::default();
+        let mut to_stock = ResourceSizeTable::default();
+        from_stock.set("Actor/Pack/A.bactorpack", 1000);
+        to_stock.set("Actor/Pack/A.bactorpack", 1500);
+        from_stock.set("Actor/Pack/WiiU.bactorpack", 1000);
+        to_stock.set("Actor/Pack/Switch.bactorpack", 1500);
+        let mut table = from_stock.clone();
+        table.remove("Actor/Pack/A.bactorpack");
+        let report = table.port(
+            &from_stock,
+            &to_stock,
+            Platform::Switch,
+            std::iter::empty::<(&str, &[u8])>(),
+        );
+        assert_eq!(report.table.get("Actor/Pack/A.bactorpack"), None);
+        assert_eq!(report.table.get("Actor/Pack/WiiU.bactorpack"), None);
+        assert_eq!(report.table.get("Actor/Pack/Switch.bactorpack"), Some(1500));
+        assert_eq!(report.extrapolated().count(), 0);
+    }
+
+    #[cfg(feature = "botw-data")]
+    #[test]
+    fn port_stock() {
+        let stock_wiiu = ResourceSizeTable::new_from_stock(Platform::WiiU);
+        let stock_nx = ResourceSizeTable::new_from_stock(Platform::Switch);
+        let report = stock_wiiu.port(
+            &stock_wiiu,
+            &stock_nx,
+            Platform::Switch,
+            std::iter::empty::<(&str, &[u8])>(),
+        );
+        assert_eq!(report.table, stock_nx);
+        assert_eq!(report.entries.len(), stock_nx.len());
+        assert_eq!(report.extrapolated().count(), 0);
+    }
+}
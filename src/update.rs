@@ -0,0 +1,158 @@
+use crate::{calc, Platform, ResourceSizeTable, CRC32};
+
+/// What to do with an entry when no value can be calculated for it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OnFailure {
+    /// Remove the existing entry, if any.
+    #[default]
+    Remove,
+    /// Leave the existing entry, if any, as it is.
+    Keep,
+    /// Fall back to an estimate from the file size alone, removing the
+    /// existing entry if even that is not possible.
+    SizeEstimate,
+}
+
+/// Controls how [`ResourceSizeTable::set_from_slice_with_policy()`] updates
+/// an entry. The default matches [`ResourceSizeTable::set_from_slice()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UpdatePolicy {
+    /// Estimate values for types without an infallible calculation, as
+    /// [`ResourceSizeTable::set_from_slice_with_estimate()`] does.
+    pub estimate: bool,
+    pub on_failure: OnFailure,
+    /// Never lower an existing value.
+    pub keep_if_larger: bool,
+    /// Only update entries already in the RSTB, never add new ones.
+    pub only_existing: bool,
+}
+
+/// The change made to an RSTB entry by a policy update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpdateAction {
+    /// A new entry was added with this value.
+    Added(u32),
+    /// An existing entry was changed.
+    Updated { old: u32, new: u32 },
+    /// The existing entry was left with this value.
+    Kept(u32),
+    /// The existing entry, with this value, was removed.
+    Removed(u32),
+    /// There was no entry and none was added.
+    Skipped,
+}
+
+impl ResourceSizeTable {
+    /// Sets the RSTB value for the specified resource name by calculating it
+    /// from its data as a byte slice, following the given policy, and
+    /// returns the change made.
+    pub fn set_from_slice_with_policy<S: AsRef<str>, B: AsRef<[u8]>, P: Into<Platform>>(
+        &mut self,
+        entry: S,
+        data: B,
+        platform: P,
+        policy: &UpdatePolicy,
+    ) -> UpdateAction {
+        let (name, data, platform) = (entry.as_ref(), data.as_ref(), platform.into());
+        let existing = self.get(name);
+        let value = if policy.estimate {
+            calc::estimate_from_slice_and_name(data, name, platform)
+        } else {
+            calc::calc_from_slice_and_name(data, name, platform)
+        };
+        let value = match (value, policy.on_failure) {
+            (Some(value), _) => Some(value),
+            (None, OnFailure::SizeEstimate) => {
+                let filesize = calc::data_size(data);
+                calc::estimate_from_size_and_name(filesize, name, platform)
+            }
+            (None, OnFailure::Keep) => {
+                return existing.map_or(UpdateAction::Skipped, UpdateAction::Kept)
+            }
+            (None, OnFailure::Remove) => None,
+        };
+        match (value, existing) {
+            (None, None) => UpdateAction::Skipped,
+            (None, Some(old)) => {
+                self.remove(name);
+                UpdateAction::Removed(old)
+            }
+            (Some(_), None) if policy.only_existing => UpdateAction::Skipped,
+            (Some(new), None) => {
+                self.crc_map.insert(CRC32.checksum(name.as_bytes()), new);
+                UpdateAction::Added(new)
+            }
+            (Some(new), Some(old)) if new == old || (policy.keep_if_larger && old > new) => {
+                UpdateAction::Kept(old)
+            }
+            (Some(new), Some(old)) => {
+                self.crc_map.insert(CRC32.checksum(name.as_bytes()), new);
+                UpdateAction::Updated { old, new }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_policies() {
+        let name = "Actor/Pack/Test.bactorpack";
+        let data = std::fs::read("test/AirOcta_Tag.sbactorpack").unwrap();
+        let value = calc::calc_from_slice_and_name(&data, name, Platform::Switch).unwrap();
+        let mut table = ResourceSizeTable::default();
+        let only_existing = UpdatePolicy {
+            only_existing: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            table.set_from_slice_with_policy(name, &data, Platform::Switch, &only_existing),
+            UpdateAction::Skipped
+        );
+        assert_eq!(
+            table.set_from_slice_with_policy(name, &data, Platform::Switch, &Default::default()),
+            UpdateAction::Added(value)
+        );
+        table.set(name, value + 1);
+        let keep_if_larger = UpdatePolicy {
+            keep_if_larger: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            table.set_from_slice_with_policy(name, &data, Platform::Switch, &keep_if_larger),
+            UpdateAction::Kept(value + 1)
+        );
+
+        // No calculation is possible for a bfres without estimates
+        let name = "Model/Test.bfres";
+        let data = std::fs::read("test/FldObj_TreeRootTropical_A_Far.sbfres").unwrap();
+        table.set(name, 100);
+        let keep = UpdatePolicy {
+            on_failure: OnFailure::Keep,
+            ..Default::default()
+        };
+        assert_eq!(
+            table.set_from_slice_with_policy(name, &data, Platform::WiiU, &keep),
+            UpdateAction::Kept(100)
+        );
+        let size_estimate = UpdatePolicy {
+            on_failure: OnFailure::SizeEstimate,
+            ..Default::default()
+        };
+        let estimate = calc::estimate_from_size_and_name(85248, name, Platform::WiiU).unwrap();
+        assert_eq!(
+            table.set_from_slice_with_policy(name, &data, Platform::WiiU, &size_estimate),
+            UpdateAction::Updated {
+                old: 100,
+                new: estimate,
+            }
+        );
+        assert_eq!(
+            table.set_from_slice_with_policy(name, &data, Platform::WiiU, &Default::default()),
+            UpdateAction::Removed(estimate)
+        );
+        assert!(!table.contains(name));
+    }
+}